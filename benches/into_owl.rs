@@ -0,0 +1,143 @@
+//! Benchmarks for the translation of OBO documents to OWL.
+//!
+//! The `tests/data/ms.obo` document is always benchmarked. Other documents,
+//! such as large ontologies downloaded from the OBO Foundry, can be added
+//! with a list of paths in the `FASTOBO_OWL_BENCH` environment variable,
+//! separated like the `PATH` variable.
+
+extern crate criterion;
+extern crate fastobo;
+extern crate fastobo_owl;
+extern crate horned_owl;
+
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BatchSize;
+use criterion::Criterion;
+use criterion::Throughput;
+use fastobo::ast as obo;
+use fastobo::semantics::Identified;
+use fastobo_owl::Context;
+use fastobo_owl::IntoOwl;
+use fastobo_owl::IntoOwlCtx;
+use horned_owl::model::AnnotatedComponent;
+use horned_owl::ontology::component_mapped::ComponentMappedOntology;
+use horned_owl::ontology::set::SetOntology;
+
+/// The component-mapped ontology type used by the OWL writers.
+type MappedOntology = ComponentMappedOntology<String, Rc<AnnotatedComponent<String>>>;
+
+/// Load the documents to benchmark, with their file name.
+fn documents() -> Vec<(String, obo::OboDoc)> {
+    let mut paths = vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/ms.obo")];
+    if let Some(var) = std::env::var_os("FASTOBO_OWL_BENCH") {
+        paths.extend(std::env::split_paths(&var));
+    }
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let doc = fastobo::from_file(&path).expect("could not parse benchmark document");
+            (name, doc)
+        })
+        .collect()
+}
+
+/// Prepare a document like `IntoOwl::into_owl` does before translation.
+fn prepare(mut doc: obo::OboDoc) -> obo::OboDoc {
+    doc.assign_namespaces().ok();
+    doc.treat_xrefs();
+    doc
+}
+
+fn context(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Context::from_obodoc");
+    for (name, doc) in documents() {
+        let doc = prepare(doc);
+        group.bench_function(&name, |b| {
+            b.iter(|| Context::<String>::from_obodoc(&doc).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn frames(c: &mut Criterion) {
+    let mut group = c.benchmark_group("IntoOwlCtx::into_owl");
+    for (name, doc) in documents() {
+        let doc = prepare(doc);
+        let mut ctx = Context::<String>::from_obodoc(&doc).unwrap();
+
+        let terms: Vec<obo::TermFrame> = doc
+            .entities()
+            .iter()
+            .flat_map(obo::EntityFrame::as_term)
+            .cloned()
+            .collect();
+        group.throughput(Throughput::Elements(terms.len() as u64));
+        group.bench_function(format!("{}/term", name), |b| {
+            b.iter_batched(
+                || terms.clone(),
+                |terms| {
+                    for frame in terms {
+                        ctx.current_frame = frame.as_id().clone().into_owl(&mut ctx);
+                        frame.into_owl(&mut ctx);
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        let typedefs: Vec<obo::TypedefFrame> = doc
+            .entities()
+            .iter()
+            .flat_map(obo::EntityFrame::as_typedef)
+            .cloned()
+            .collect();
+        group.throughput(Throughput::Elements(typedefs.len() as u64));
+        group.bench_function(format!("{}/typedef", name), |b| {
+            b.iter_batched(
+                || typedefs.clone(),
+                |typedefs| {
+                    for frame in typedefs {
+                        ctx.current_frame = frame.as_id().clone().into_owl(&mut ctx);
+                        frame.into_owl(&mut ctx);
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn document(c: &mut Criterion) {
+    let mut group = c.benchmark_group("IntoOwl::into_owl");
+    for (name, doc) in documents() {
+        group.throughput(Throughput::Elements(doc.entities().len() as u64));
+        group.bench_function(format!("{}/SetOntology", name), |b| {
+            b.iter_batched(
+                || doc.clone(),
+                |doc| doc.into_owl::<SetOntology<String>>().unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(format!("{}/ComponentMappedOntology", name), |b| {
+            b.iter_batched(
+                || doc.clone(),
+                |doc| doc.into_owl::<MappedOntology>().unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = context, frames, document
+}
+criterion_main!(benches);
@@ -0,0 +1,99 @@
+//! Memory usage of the translation of OBO documents to OWL.
+//!
+//! This reports the peak heap usage and the size of the result of each
+//! translation step, using a global allocator counting allocated bytes.
+//! Since `IntoOwl::into_owl` consumes its input, the reported peak usage
+//! of these steps includes a copy of the document.
+//! Documents are selected like in the `into_owl` benchmarks, using the
+//! `FASTOBO_OWL_BENCH` environment variable.
+
+extern crate fastobo;
+extern crate fastobo_owl;
+extern crate horned_owl;
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use fastobo::ast as obo;
+use fastobo_owl::Context;
+use fastobo_owl::IntoOwl;
+use horned_owl::model::AnnotatedComponent;
+use horned_owl::ontology::component_mapped::ComponentMappedOntology;
+use horned_owl::ontology::set::SetOntology;
+
+/// The component-mapped ontology type used by the OWL writers.
+type MappedOntology = ComponentMappedOntology<String, Rc<AnnotatedComponent<String>>>;
+
+/// A global allocator recording the current and peak heap usage.
+struct Counter;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+/// Run a translation step and report its peak and retained heap usage.
+fn measure<T, F: FnOnce() -> T>(name: &str, step: &str, f: F) {
+    let before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let result = f();
+    let peak = PEAK.load(Ordering::Relaxed) - before;
+    let retained = CURRENT.load(Ordering::Relaxed).saturating_sub(before);
+    drop(result);
+    println!(
+        "{:<16} {:<40} {:>10} KiB {:>10} KiB",
+        name,
+        step,
+        peak / 1024,
+        retained / 1024,
+    );
+}
+
+fn main() {
+    let mut paths = vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/ms.obo")];
+    if let Some(var) = std::env::var_os("FASTOBO_OWL_BENCH") {
+        paths.extend(std::env::split_paths(&var));
+    }
+
+    println!(
+        "{:<16} {:<40} {:>14} {:>14}",
+        "document", "step", "peak", "retained"
+    );
+    for path in paths {
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let doc: obo::OboDoc =
+            fastobo::from_file(&path).expect("could not parse benchmark document");
+
+        measure(&name, "Context::from_obodoc", || {
+            Context::<String>::from_obodoc(&doc).unwrap()
+        });
+        measure(&name, "IntoOwl::into_owl (SetOntology)", || {
+            doc.clone().into_owl::<SetOntology<String>>().unwrap()
+        });
+        measure(&name, "IntoOwl::into_owl (ComponentMapped)", || {
+            doc.clone().into_owl::<MappedOntology>().unwrap()
+        });
+    }
+}
@@ -5,6 +5,7 @@ extern crate horned_owl;
 
 use std::rc::Rc;
 
+use fastobo_owl::Config;
 use fastobo_owl::IntoOwl;
 use fastobo_owl::IntoOwlPrefixes;
 use horned_owl::model::AnnotatedComponent;
@@ -21,10 +22,16 @@ fn main() {
             Err(e) => panic!("{:?} could not be parsed:\n{}", path, e),
         };
 
-        // Convert to OWL
+        // Convert to OWL, using the file name if the `ontology` clause is missing
         let prefixes = obodoc.prefixes();
-        let owldoc: ComponentMappedOntology<String, Rc<AnnotatedComponent<String>>> =
-            obodoc.into_owl::<SetOntology<String>>().unwrap().into();
+        let config = Config {
+            default_ontology: path.file_stem().map(|s| s.to_string_lossy().into_owned()),
+            ..Config::default()
+        };
+        let owldoc: ComponentMappedOntology<String, Rc<AnnotatedComponent<String>>> = obodoc
+            .into_owl_with_config::<SetOntology<String>>(config)
+            .unwrap()
+            .into();
 
         // Write it back
         let file = std::fs::File::create(path.with_extension("owl")).unwrap();
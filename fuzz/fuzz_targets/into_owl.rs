@@ -0,0 +1,13 @@
+#![no_main]
+
+//! Fuzz the translation of documents parsed from arbitrary text.
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        if let Ok(doc) = fastobo::from_str(text) {
+            fastobo_owl_fuzz::check(doc);
+        }
+    }
+});
@@ -0,0 +1,41 @@
+//! Shared assertions for the `fastobo-owl` fuzz targets.
+
+extern crate fastobo;
+extern crate fastobo_owl;
+extern crate horned_owl;
+
+use std::rc::Rc;
+
+use fastobo::ast::OboDoc;
+use fastobo_owl::IntoOwl;
+use fastobo_owl::IntoOwlPrefixes;
+use horned_owl::model::AnnotatedComponent;
+use horned_owl::model::Component;
+use horned_owl::ontology::component_mapped::ComponentMappedOntology;
+use horned_owl::ontology::set::SetOntology;
+
+/// Translate a document and check the produced ontology is well formed.
+///
+/// Translation errors are expected for invalid documents, but the
+/// translation must never panic, and a successful translation must produce
+/// an ontology with a single ontology ID that can be serialized.
+///
+/// NB: the OWL functional syntax writer of `horned-owl` panics on some
+///     non-ASCII literals, so only OWL/XML serialization is checked.
+pub fn check(doc: OboDoc) {
+    let prefixes = doc.prefixes();
+    let ont = match doc.into_owl::<SetOntology<String>>() {
+        Ok(ont) => ont,
+        Err(_) => return,
+    };
+
+    let ids = ont
+        .iter()
+        .filter(|ac| matches!(ac.component, Component::OntologyID(_)))
+        .count();
+    assert_eq!(ids, 1, "ontology should have exactly one ontology ID");
+
+    let ont: ComponentMappedOntology<String, Rc<AnnotatedComponent<String>>> = ont.into();
+    horned_owl::io::owx::writer::write(Vec::new(), &ont, Some(&prefixes))
+        .expect("ontology should be serializable to OWL/XML");
+}
@@ -0,0 +1,288 @@
+//! Configuration of the OBO to OWL translation.
+
+/// The options controlling how an OBO document is translated to OWL.
+///
+/// The default configuration follows the OBO 1.4 specification, and should
+/// be used unless the produced ontology needs to be tweaked for a particular
+/// consumer.
+///
+/// # Example
+/// ```rust
+/// # use horned_owl::ontology::set::SetOntology;
+/// use fastobo_owl::Config;
+/// use fastobo_owl::IntoOwl;
+///
+/// let doc = fastobo::from_str("ontology: test\n").unwrap();
+/// let config = Config {
+///     strict_literals: true,
+///     ..Config::default()
+/// };
+/// let ont = doc.into_owl_with_config::<SetOntology<String>>(config);
+/// assert!(ont.is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Whether to reject property value literals invalid for their datatype.
+    ///
+    /// Literals with a well-known XSD datatype are always converted to their
+    /// canonical form when they are valid. Invalid literals are kept as-is
+    /// by default (and reported by
+    /// [`IntoOwl::into_owl_with_warnings`](./trait.IntoOwl.html#tymethod.into_owl_with_warnings)),
+    /// but will cause the conversion to fail with an
+    /// [`Error::InvalidLiteral`](./enum.Error.html#variant.InvalidLiteral)
+    /// if this flag is set.
+    pub strict_literals: bool,
+
+    /// Whether to translate typedefs with a datatype range to data properties.
+    ///
+    /// By default, all typedefs that are not metadata tags are translated
+    /// to object properties, and `property_value` clauses are always
+    /// translated to annotations. When this flag is set, typedefs with a
+    /// `range` in the XSD namespace are declared as data properties instead,
+    /// and `property_value` clauses using them with a literal value become
+    /// `DataPropertyAssertion` axioms on instances, or `DataHasValue`
+    /// restrictions on classes, so that they can be used for reasoning.
+    pub data_properties: bool,
+
+    /// The IRI prefix used to skolemise frames declared with `is_anonymous`.
+    ///
+    /// Anonymous terms and typedefs are given an IRI made of this prefix
+    /// followed by their local OBO identifier (or their percent-encoded URL
+    /// identifier), instead of the IRI their identifier would normally
+    /// expand to, and are not annotated with an `oboInOwl:id`. Anonymous
+    /// instances are translated to OWL anonymous individuals when
+    /// [`Config::data_properties`](#structfield.data_properties) is set, and
+    /// are otherwise ignored like all instances. When unset, the `.well-known/genid/` path under the
+    /// ontology IRI is used (e.g. `http://purl.obolibrary.org/obo/go/.well-known/genid/`).
+    pub skolem_prefix: Option<String>,
+
+    /// How to translate the terms declared with `is_obsolete: true`.
+    pub obsoletion: ObsoletionPolicy,
+
+    /// Whether to declare all the entities used in the produced ontology.
+    ///
+    /// Only the entities with their own frame are declared by the
+    /// translation itself, but OWL 2 DL requires all entities to be
+    /// declared, including for instance the annotation properties used in
+    /// `property_value` clauses, or the classes referenced by `is_a` clauses
+    /// without being defined in the document. When this flag is set (the
+    /// default), the missing declaration axioms are added to the ontology.
+    pub declare_entities: bool,
+
+    /// How to handle IRIs used in roles incompatible with OWL 2 DL.
+    pub role_conflicts: RolePolicy,
+
+    /// The OWL 2 profile the produced ontology must conform to.
+    ///
+    /// When set, every axiom produced by the translation is checked against
+    /// the profile, and the out-of-profile axioms are handled according to
+    /// [`Config::profile_policy`](#structfield.profile_policy).
+    pub profile: Option<Profile>,
+
+    /// How to handle the axioms outside of the requested OWL 2 profile.
+    pub profile_policy: ProfilePolicy,
+
+    /// The IRI of an annotation property used to record axiom provenance.
+    ///
+    /// When set, every axiom produced from an OBO clause is annotated with
+    /// the identifier of its frame and the tag of its clause, separated by a
+    /// space (e.g. `"GO:0000001 is_a"`). Axioms produced from the header
+    /// frame are only annotated with their clause tag, and axioms not
+    /// produced from a particular clause (such as entity declarations) are
+    /// only annotated with their frame identifier. Source line numbers are
+    /// not recorded, since they are not exposed by the OBO syntax tree.
+    pub provenance: Option<String>,
+
+    /// Whether to keep the comments of OBO lines as axiom annotations.
+    ///
+    /// OBO lines may end with a `! comment`, which is not part of the
+    /// semantics of the clause. These comments are dropped by default, but
+    /// when this flag is set, they are translated to `rdfs:comment`
+    /// annotations on the axiom produced from the line.
+    pub line_comments: bool,
+
+    /// The base IRI used to build the ontology IRI from its name.
+    ///
+    /// The IRI of an ontology is built from the value of its `ontology`
+    /// header clause, followed by an `.owl` extension, under this base
+    /// (e.g. `https://w3id.org/example/` for `ontology: test` gives the
+    /// `https://w3id.org/example/test.owl` ontology IRI). When unset, the
+    /// OBO PURL namespace `http://purl.obolibrary.org/obo/` is used. An
+    /// `ontology` clause containing a URL is always used as-is instead.
+    pub base_iri: Option<String>,
+
+    /// The identifier or IRI of the ontology, if missing from the document.
+    ///
+    /// OBO documents must declare their identifier with an `ontology`
+    /// header clause, which is used to build the ontology IRI. When this
+    /// option is set, it is used in place of a missing `ontology` clause
+    /// instead of failing with an
+    /// [`Error::Cardinality`](./enum.Error.html#variant.Cardinality).
+    /// For ad-hoc documents, it can be derived from the file name:
+    ///
+    /// ```rust
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// use fastobo_owl::Config;
+    /// use fastobo_owl::IntoOwl;
+    ///
+    /// let path = std::path::Path::new("tests/data/slim.obo");
+    /// let doc = fastobo::from_str("format-version: 1.4\n").unwrap();
+    /// let config = Config {
+    ///     default_ontology: path.file_stem().map(|s| s.to_string_lossy().into_owned()),
+    ///     ..Config::default()
+    /// };
+    /// let ont = doc.into_owl_with_config::<SetOntology<String>>(config);
+    /// assert!(ont.is_ok());
+    /// ```
+    pub default_ontology: Option<String>,
+
+    /// How to render the dates of `date` and `creation_date` clauses.
+    pub dates: DatePolicy,
+
+    /// How to handle synonyms inconsistent with the `synonymtypedef` clauses.
+    pub synonym_types: SynonymTypePolicy,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            strict_literals: false,
+            data_properties: false,
+            skolem_prefix: None,
+            obsoletion: ObsoletionPolicy::default(),
+            declare_entities: true,
+            role_conflicts: RolePolicy::default(),
+            profile: None,
+            profile_policy: ProfilePolicy::default(),
+            provenance: None,
+            line_comments: false,
+            base_iri: None,
+            default_ontology: None,
+            dates: DatePolicy::default(),
+            synonym_types: SynonymTypePolicy::default(),
+        }
+    }
+}
+
+/// The policy used to translate obsolete terms.
+///
+/// Obsolete terms are always annotated with `owl:deprecated`. However, the
+/// [OBO Foundry principles](https://obofoundry.org/principles/fp-000-summary.html)
+/// require that obsolete classes are not used in logical axioms in released
+/// ontologies, and that their label starts with `obsolete`.
+///
+/// With any policy other than [`ObsoletionPolicy::Keep`], the targets of
+/// the `replaced_by` and `consider` clauses of obsolete terms must be
+/// declared in the document, or the conversion will fail with an
+/// [`Error::InvalidFrame`](./enum.Error.html#variant.InvalidFrame).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ObsoletionPolicy {
+    /// Translate obsolete terms like any other term.
+    #[default]
+    Keep,
+    /// Remove the `SubClassOf`, `EquivalentClasses` and `DisjointClasses`
+    /// axioms of obsolete terms.
+    Strip,
+    /// Remove the logical axioms of obsolete terms like
+    /// [`ObsoletionPolicy::Strip`], and prefix their label with `obsolete`.
+    Relabel,
+}
+
+/// The policy used to render dates.
+///
+/// The `date` header clause uses the legacy `dd:MM:yyyy HH:mm` OBO format,
+/// while `creation_date` clauses use ISO 8601 dates or datetimes. Note that
+/// `creation_date` values in any other format are rejected by the OBO
+/// parser, and never reach the translation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DatePolicy {
+    /// Render datetimes as `xsd:dateTime` literals, and dates without a
+    /// time component as `xsd:date` literals.
+    #[default]
+    Xsd,
+    /// Render all dates as `xsd:dateTime` literals, using midnight for
+    /// dates without a time component.
+    DateTime,
+    /// Render dates as plain literals in their OBO syntax, like the OWL API
+    /// (e.g. `"08:05:2019 13:02"` for the `date` header clause).
+    Obo,
+}
+
+/// The policy used for synonyms inconsistent with their synonym type.
+///
+/// The type of a synonym should be declared with a `synonymtypedef` header
+/// clause, which may also declare the scope of all the synonyms of that
+/// type (e.g. `synonymtypedef: systematic_synonym "Systematic synonym" EXACT`).
+/// Only the declared synonym types are translated to subproperties of
+/// `oboInOwl:SynonymTypeProperty`, so a synonym with an undeclared type is
+/// annotated with an IRI that is not declared anywhere.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SynonymTypePolicy {
+    /// Translate synonyms as they are written, reporting the synonyms with
+    /// an undeclared type or a mismatched scope as warnings.
+    #[default]
+    Ignore,
+    /// Fail the translation with an
+    /// [`Error::InvalidFrame`](./enum.Error.html#variant.InvalidFrame) for
+    /// synonyms with an undeclared type, or with a scope different from the
+    /// one declared by their type.
+    Error,
+    /// Drop the undeclared types of synonyms, and use the scope declared by
+    /// the type of a synonym in place of its own scope.
+    Repair,
+}
+
+/// The policy used for IRIs used in conflicting roles.
+///
+/// OWL 2 DL forbids using the same IRI for different kinds of properties,
+/// or as both a class and a datatype. This can happen for instance when a
+/// typedef declared with `is_metadata_tag` (translated to an annotation
+/// property) is used in an `intersection_of` clause (which requires an
+/// object property).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RolePolicy {
+    /// Keep all axioms, even if the produced ontology is not in OWL 2 DL.
+    #[default]
+    Ignore,
+    /// Fail the translation with an
+    /// [`Error::RoleConflicts`](./enum.Error.html#variant.RoleConflicts)
+    /// reporting all conflicting IRIs and their originating frames.
+    Error,
+    /// Remove the axioms using an IRI in a role conflicting with its
+    /// declaration (e.g. a metadata tag used as an object property), or
+    /// in any of its conflicting roles if it is not declared.
+    Drop,
+}
+
+/// An OWL 2 profile, as defined in the [OWL 2 Web Ontology Language Profiles](https://www.w3.org/TR/owl2-profiles/).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// OWL 2 EL, for ontologies with large numbers of classes and properties.
+    EL,
+    /// OWL 2 QL, for query answering over large volumes of instance data.
+    QL,
+    /// OWL 2 RL, for rule-based reasoning.
+    RL,
+    /// OWL 2 DL, the syntactic restrictions of OWL 2 ensuring decidability.
+    DL,
+}
+
+/// The policy used for axioms outside of the requested OWL 2 profile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProfilePolicy {
+    /// Fail the translation with an
+    /// [`Error::ProfileViolations`](./enum.Error.html#variant.ProfileViolations)
+    /// reporting all the violations with their originating frame and clause.
+    #[default]
+    Error,
+    /// Remove the axioms outside of the profile.
+    Drop,
+    /// Weaken the axioms outside of the profile when possible, and remove
+    /// them otherwise.
+    ///
+    /// Equivalence axioms are weakened to subclass axioms, and qualified
+    /// cardinality restrictions (such as the ones produced by `cardinality`
+    /// or `minCardinality` qualifiers) are replaced with existential
+    /// restrictions.
+    Approximate,
+}
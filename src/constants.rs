@@ -10,6 +10,8 @@ macro_rules! uris {
 
 uris! {
     dc => "http://purl.org/dc/elements/1.1/",
+    dcterms => "http://purl.org/dc/terms/",
+    foaf => "http://xmlns.com/foaf/0.1/",
     obo => "http://purl.obolibrary.org/obo/",
     oboInOwl => "http://www.geneontology.org/formats/oboInOwl#",
     owl => "http://www.w3.org/2002/07/owl#",
@@ -23,6 +25,8 @@ uris! {
 
 pub mod uri {
     pub const DC: &str = dc!();
+    pub const DCTERMS: &str = dcterms!();
+    pub const FOAF: &str = foaf!();
     pub const OBO: &str = obo!();
     pub const OBO_IN_OWL: &str = oboInOwl!();
     pub const OWL: &str = owl!();
@@ -32,6 +36,16 @@ pub mod uri {
     pub const XSD: &str = xsd!();
 }
 
+// --- Class URIs ------------------------------------------------------------
+
+/// Built-in classes.
+pub mod class {
+    /// OWL built-in classes.
+    pub mod owl {
+        pub const THING: &str = concat!(owl!(), "Thing");
+    }
+}
+
 // --- Datatype URIs ---------------------------------------------------------
 
 /// Datatypes used in OBO to OWL translation.
@@ -42,6 +56,19 @@ pub mod datatype {
         pub const BOOLEAN: &str = concat!(xsd!(), "boolean");
         pub const DATE: &str = concat!(xsd!(), "date");
         pub const DATETIME: &str = concat!(xsd!(), "dateTime");
+        pub const INTEGER: &str = concat!(xsd!(), "integer");
+        pub const DECIMAL: &str = concat!(xsd!(), "decimal");
+        pub const ANY_URI: &str = concat!(xsd!(), "anyURI");
+    }
+
+    /// RDF datatypes.
+    pub mod rdf {
+        pub const LANG_STRING: &str = concat!(rdf!(), "langString");
+    }
+
+    /// RDF Schema datatypes.
+    pub mod rdfs {
+        pub const LITERAL: &str = concat!(rdfs!(), "Literal");
     }
 }
 
@@ -78,6 +105,7 @@ pub mod property {
     /// OWL2 annotation properties.
     pub mod owl {
         pub const DEPRECATED: &str = concat!(owl!(), "deprecated");
+        pub const VERSION_INFO: &str = concat!(owl!(), "versionInfo");
     }
 
     /// RDF Schema annotation properties.
@@ -92,6 +120,11 @@ pub mod property {
         pub const DATE: &str = concat!(dc!(), "date");
     }
 
+    /// Dublin core terms annotation properties.
+    pub mod dcterms {
+        pub const LICENSE: &str = concat!(dcterms!(), "license");
+    }
+
     /// Information artifact ontology annotation properties.
     pub mod iao {
         pub const REPLACED_BY: &str = concat!(obo!(), "IAO_0100001");
@@ -101,3 +134,12 @@ pub mod property {
         pub const IS_ANTI_SYMETRIC: &str = concat!(obo!(), "IAO_0000427");
     }
 }
+
+// --- License URIs ----------------------------------------------------------
+
+/// Canonical IRIs of the licenses commonly used by OBO ontologies.
+pub mod license {
+    pub const CC_BY_3_0: &str = "https://creativecommons.org/licenses/by/3.0/";
+    pub const CC_BY_4_0: &str = "https://creativecommons.org/licenses/by/4.0/";
+    pub const CC0_1_0: &str = "https://creativecommons.org/publicdomain/zero/1.0/";
+}
@@ -9,6 +9,28 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     /// An error caused by a clause appearing an invalid number of times.
     ///
+    /// For instance, the `ontology` clause must appear exactly once in the
+    /// header frame of an OBO document: a document with no `ontology` clause
+    /// will error when attempting to translate it, unless a
+    /// [`Config::default_ontology`] is given.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use fastobo::ast::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// use fastobo_owl::IntoOwl;
+    ///
+    /// let doc = OboDoc::new();
+    /// let res = doc.into_owl::<SetOntology<String>>();
+    /// assert!(matches!(res, Err(fastobo_owl::Error::Cardinality(_))));
+    /// ```
+    ///
+    /// [`Config::default_ontology`]: ./struct.Config.html#structfield.default_ontology
+    #[error(transparent)]
+    Cardinality(#[from] CardinalityError),
+
+    /// An error caused by an entity frame with an invalid set of clauses.
+    ///
     /// For instance, OBO frames have `union_of` clauses, that can not appear
     /// only once: they must appear zero, or more than two times. Having a
     /// frame with a single `union_of` clause will error when attempting to
@@ -16,22 +38,30 @@ pub enum Error {
     ///
     /// # Example:
     /// ```rust
-    /// # use std::str::FromStr;
     /// # use fastobo::ast::*;
     /// # use horned_owl::ontology::set::SetOntology;
     /// use fastobo_owl::IntoOwl;
-    /// use fastobo_owl::IntoOwlPrefixes;
+    /// use fastobo_owl::FrameError;
     ///
     /// let mut frame = TermFrame::new(ClassIdent::from(PrefixedIdent::new("TST", "001")));
     /// let id = Box::new(ClassIdent::from(PrefixedIdent::new("TST", "002")));
     /// frame.push(Line::from(TermClause::UnionOf(id)));
+    /// frame.push(Line::from(TermClause::Namespace(Box::new(UnprefixedIdent::new("test").into()))));
     ///
-    /// let doc = OboDoc::with_entities(vec![EntityFrame::from(frame)]);
+    /// let mut doc = OboDoc::with_entities(vec![EntityFrame::from(frame)]);
+    /// doc.header_mut().push(HeaderClause::Ontology(Box::new("tst".into())));
     /// let res = doc.into_owl::<SetOntology<String>>();
-    /// assert!(matches!(res, Err(fastobo_owl::Error::Cardinality(_))));
+    /// assert!(matches!(
+    ///     res,
+    ///     Err(fastobo_owl::Error::InvalidFrame { error: FrameError::Cardinality(_), .. })
+    /// ));
     /// ```
-    #[error(transparent)]
-    Cardinality(#[from] CardinalityError),
+    #[error("invalid frame {id}: {error}")]
+    InvalidFrame {
+        id: fastobo::ast::Ident,
+        #[source]
+        error: FrameError,
+    },
 
     #[error(transparent)]
     /// An error caused by an element in invalid syntax.
@@ -41,4 +71,250 @@ pub enum Error {
     /// invalid data.
     ///
     Syntax(#[from] SyntaxError),
+
+    /// An error caused by a literal invalid for its datatype.
+    ///
+    /// This is only raised when [`Config::strict_literals`] is set, for
+    /// `property_value` clauses with a well-known XSD datatype but a value
+    /// outside of its lexical space (e.g. `"1.5" xsd:integer`), or with a
+    /// `lang` qualifier that is not a valid language tag or is used on a
+    /// literal which is not a string (e.g. `"1" xsd:integer {lang="en"}`).
+    /// Otherwise, invalid literals are kept as-is, and reported as warnings by
+    /// [`IntoOwl::into_owl_with_warnings`].
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// use fastobo_owl::Config;
+    /// use fastobo_owl::IntoOwl;
+    ///
+    /// let doc = fastobo::from_str(r#"ontology: test
+    /// property_value: IAO:0000001 "1.5" xsd:integer
+    /// "#).unwrap();
+    /// let (_, warnings) = doc.clone()
+    ///     .into_owl_with_warnings::<SetOntology<String>>(Config::default())
+    ///     .unwrap();
+    /// assert!(matches!(warnings[0], fastobo_owl::Error::InvalidLiteral { frame: None, .. }));
+    ///
+    /// let config = Config { strict_literals: true, ..Default::default() };
+    /// let res = doc.into_owl_with_config::<SetOntology<String>>(config);
+    /// assert!(matches!(res, Err(fastobo_owl::Error::InvalidLiteral { .. })));
+    /// ```
+    ///
+    /// [`Config::strict_literals`]: ./struct.Config.html#structfield.strict_literals
+    /// [`IntoOwl::into_owl_with_warnings`]: ./trait.IntoOwl.html#tymethod.into_owl_with_warnings
+    #[error(
+        "invalid literal {literal:?} for datatype <{datatype}>{}",
+        .frame.as_ref().map(|id| format!(" in frame {}", id)).unwrap_or_default()
+    )]
+    InvalidLiteral {
+        /// The identifier of the frame of the literal, or `None` for the header frame.
+        frame: Option<fastobo::ast::Ident>,
+        literal: String,
+        datatype: String,
+    },
+
+    /// An error caused by IRIs used in roles incompatible with OWL 2 DL.
+    ///
+    /// This is only raised when [`Config::role_conflicts`] is set to
+    /// [`RolePolicy::Error`], and reports all the conflicting IRIs together
+    /// with the OBO frames they were used in.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// use fastobo_owl::Config;
+    /// use fastobo_owl::IntoOwl;
+    /// use fastobo_owl::RolePolicy;
+    ///
+    /// let doc = fastobo::from_str(r#"ontology: test
+    /// default-namespace: test
+    ///
+    /// [Term]
+    /// id: TST:001
+    /// intersection_of: TST:003
+    /// intersection_of: TST:002 TST:004
+    ///
+    /// [Typedef]
+    /// id: TST:002
+    /// is_metadata_tag: true
+    /// "#).unwrap();
+    /// let config = Config { role_conflicts: RolePolicy::Error, ..Default::default() };
+    /// let res = doc.into_owl_with_config::<SetOntology<String>>(config);
+    /// assert!(matches!(res, Err(fastobo_owl::Error::RoleConflicts(_))));
+    /// ```
+    ///
+    /// [`Config::role_conflicts`]: ./struct.Config.html#structfield.role_conflicts
+    /// [`RolePolicy::Error`]: ./enum.RolePolicy.html#variant.Error
+    #[error("{} IRI(s) used in conflicting roles", .0.len())]
+    RoleConflicts(Vec<RoleConflict>),
+
+    /// An error caused by axioms outside of the requested OWL 2 profile.
+    ///
+    /// This is only raised when [`Config::profile`] is set and
+    /// [`Config::profile_policy`] is [`ProfilePolicy::Error`], and reports
+    /// all the out-of-profile axioms together with the OBO frame and clause
+    /// they were produced from.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// use fastobo_owl::Config;
+    /// use fastobo_owl::IntoOwl;
+    /// use fastobo_owl::Profile;
+    ///
+    /// let doc = fastobo::from_str(r#"ontology: test
+    /// default-namespace: test
+    ///
+    /// [Term]
+    /// id: TST:001
+    /// union_of: TST:002
+    /// union_of: TST:003
+    /// "#).unwrap();
+    /// let config = Config { profile: Some(Profile::EL), ..Default::default() };
+    /// let res = doc.into_owl_with_config::<SetOntology<String>>(config);
+    /// assert!(matches!(res, Err(fastobo_owl::Error::ProfileViolations(_))));
+    /// ```
+    ///
+    /// [`Config::profile`]: ./struct.Config.html#structfield.profile
+    /// [`Config::profile_policy`]: ./struct.Config.html#structfield.profile_policy
+    /// [`ProfilePolicy::Error`]: ./enum.ProfilePolicy.html#variant.Error
+    #[error("{} axiom(s) outside of the requested OWL 2 profile", .0.len())]
+    ProfileViolations(Vec<ProfileViolation>),
+
+    /// An error caused by `owl-axioms` header clauses in invalid syntax.
+    ///
+    /// The content of the `owl-axioms` clauses must be valid OWL functional
+    /// syntax, and is reported with the message of the OWL parser.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// use fastobo_owl::IntoOwl;
+    ///
+    /// let doc = fastobo::from_str(r#"ontology: test
+    /// owl-axioms: Prefix(owl:=<http://www.w3.org/2002/07/owl#>) Ontology(
+    /// "#).unwrap();
+    /// let res = doc.into_owl::<SetOntology<String>>();
+    /// assert!(matches!(res, Err(fastobo_owl::Error::InvalidOwlAxioms(_))));
+    /// ```
+    #[error("invalid owl-axioms clause: {0}")]
+    InvalidOwlAxioms(String),
+}
+
+/// The error type for entity frames violating the OBO 1.4 semantics.
+#[derive(Debug, Error, PartialEq)]
+pub enum FrameError {
+    /// A clause appears an invalid number of times in the frame.
+    ///
+    /// This is the case of a frame with a single `intersection_of` or
+    /// `union_of` clause, or with several `is_anonymous` clauses.
+    #[error(transparent)]
+    Cardinality(#[from] CardinalityError),
+
+    /// The frame is defined both as an intersection and as a union.
+    #[error("frame has both `intersection_of` and `union_of` clauses")]
+    MixedDefinitions,
+
+    /// The frame has differentia but no genus in its logical definition.
+    ///
+    /// This is the case of a term frame with `intersection_of: R X` clauses
+    /// but no `intersection_of: X` clause.
+    #[error("frame has `intersection_of` differentia without a genus")]
+    MissingGenus,
+
+    /// The frame references a replacement term not declared in the document.
+    ///
+    /// This is only checked for the `replaced_by` and `consider` clauses
+    /// of obsolete terms, when an [`ObsoletionPolicy`] other than the
+    /// default is used.
+    ///
+    /// [`ObsoletionPolicy`]: ./enum.ObsoletionPolicy.html
+    #[error("replacement term {target} not found")]
+    MissingReplacement { target: fastobo::ast::Ident },
+
+    /// A cardinality qualifier does not have a non-negative integer value.
+    ///
+    /// This is checked for the `cardinality`, `minCardinality` and
+    /// `maxCardinality` qualifiers of `relationship` and `intersection_of`
+    /// clauses, which are translated to OWL cardinality restrictions.
+    #[error("invalid value {value:?} for qualifier `{key}`")]
+    InvalidQualifier { key: String, value: String },
+
+    /// A synonym has a type not declared in the header frame.
+    ///
+    /// This is only checked when [`Config::synonym_types`] is set to
+    /// [`SynonymTypePolicy::Error`].
+    ///
+    /// [`Config::synonym_types`]: ./struct.Config.html#structfield.synonym_types
+    /// [`SynonymTypePolicy::Error`]: ./enum.SynonymTypePolicy.html#variant.Error
+    #[error("undeclared synonym type {ty}")]
+    UndeclaredSynonymType { ty: fastobo::ast::SynonymTypeIdent },
+
+    /// A synonym has a scope different from the one declared by its type.
+    ///
+    /// This is only checked when [`Config::synonym_types`] is set to
+    /// [`SynonymTypePolicy::Error`].
+    ///
+    /// [`Config::synonym_types`]: ./struct.Config.html#structfield.synonym_types
+    /// [`SynonymTypePolicy::Error`]: ./enum.SynonymTypePolicy.html#variant.Error
+    #[error("synonym of type {ty} has scope {found}, expected {expected}")]
+    SynonymScopeMismatch {
+        ty: fastobo::ast::SynonymTypeIdent,
+        expected: fastobo::ast::SynonymScope,
+        found: fastobo::ast::SynonymScope,
+    },
+}
+
+/// The role of an entity in an OWL ontology.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    Class,
+    Datatype,
+    ObjectProperty,
+    DataProperty,
+    AnnotationProperty,
+    NamedIndividual,
+}
+
+impl Role {
+    /// Check whether an IRI can not be used in both roles in OWL 2 DL.
+    pub fn conflicts_with(self, other: Role) -> bool {
+        use self::Role::*;
+        matches!(
+            (self, other),
+            (Class, Datatype)
+                | (Datatype, Class)
+                | (ObjectProperty, DataProperty)
+                | (ObjectProperty, AnnotationProperty)
+                | (DataProperty, ObjectProperty)
+                | (DataProperty, AnnotationProperty)
+                | (AnnotationProperty, ObjectProperty)
+                | (AnnotationProperty, DataProperty)
+        )
+    }
+}
+
+/// An IRI used in conflicting roles in the produced ontology.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleConflict {
+    /// The IRI used in conflicting roles.
+    pub iri: String,
+    /// All the roles the IRI is used in.
+    pub roles: Vec<Role>,
+    /// The identifiers of the OBO frames using the IRI.
+    pub frames: Vec<fastobo::ast::Ident>,
+}
+
+/// An axiom outside of the requested OWL 2 profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileViolation {
+    /// The identifier of the OBO frame the axiom was produced from, if any.
+    ///
+    /// This is `None` for axioms produced from the header frame.
+    pub frame: Option<fastobo::ast::Ident>,
+    /// The tag of the OBO clause the axiom was produced from, if known.
+    pub clause: Option<String>,
+    /// A description of the construct violating the profile.
+    pub reason: String,
 }
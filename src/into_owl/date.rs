@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use fastobo::ast as obo;
 use fastobo::ast::Date;
 use fastobo::ast::DateTime;
@@ -6,16 +8,27 @@ use horned_owl::model::ForIRI;
 
 use super::Context;
 use super::IntoOwlCtx;
+use crate::config::DatePolicy;
 use crate::constants::datatype::xsd;
 
+/// Create a plain literal with the OBO syntax of a date.
+fn obo_literal<A: ForIRI, D: Display>(date: D) -> owl::Literal<A> {
+    owl::Literal::Simple {
+        literal: date.to_string(),
+    }
+}
+
 macro_rules! datetime_impl {
     ($type:ty) => {
         impl<A: ForIRI> IntoOwlCtx<A> for &$type {
             type Owl = owl::Literal<A>;
             fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
-                owl::Literal::Datatype {
-                    literal: self.to_xsd_datetime(),
-                    datatype_iri: ctx.build.iri(xsd::DATETIME),
+                match ctx.config.dates {
+                    DatePolicy::Obo => obo_literal(self),
+                    DatePolicy::Xsd | DatePolicy::DateTime => owl::Literal::Datatype {
+                        literal: self.to_xsd_datetime(),
+                        datatype_iri: ctx.build.iri(xsd::DATETIME),
+                    },
                 }
             }
         }
@@ -35,9 +48,16 @@ datetime_impl!(obo::IsoDateTime);
 impl<A: ForIRI> IntoOwlCtx<A> for &obo::IsoDate {
     type Owl = owl::Literal<A>;
     fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
-        owl::Literal::Datatype {
-            literal: self.to_xsd_date(),
-            datatype_iri: ctx.build.iri(xsd::DATE),
+        match ctx.config.dates {
+            DatePolicy::Obo => obo_literal(self),
+            DatePolicy::Xsd => owl::Literal::Datatype {
+                literal: self.to_xsd_date(),
+                datatype_iri: ctx.build.iri(xsd::DATE),
+            },
+            DatePolicy::DateTime => owl::Literal::Datatype {
+                literal: format!("{}T00:00:00", self.to_xsd_date()),
+                datatype_iri: ctx.build.iri(xsd::DATETIME),
+            },
         }
     }
 }
@@ -53,9 +73,9 @@ impl<A: ForIRI> IntoOwlCtx<A> for &obo::CreationDate {
     type Owl = owl::Literal<A>;
     fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
         match self {
-            obo::CreationDate::Date(d) => <&obo::IsoDate as IntoOwlCtx<A>>::into_owl(&d, ctx),
+            obo::CreationDate::Date(d) => <&obo::IsoDate as IntoOwlCtx<A>>::into_owl(d, ctx),
             obo::CreationDate::DateTime(dt) => {
-                <&obo::IsoDateTime as IntoOwlCtx<A>>::into_owl(&dt, ctx)
+                <&obo::IsoDateTime as IntoOwlCtx<A>>::into_owl(dt, ctx)
             }
         }
     }
@@ -67,3 +87,67 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::CreationDate {
         (&self).into_owl(ctx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::config::Config;
+
+    fn literals(dates: DatePolicy) -> Vec<owl::Literal<String>> {
+        let doc = fastobo::from_str("ontology: test\n").unwrap();
+        let config = Config {
+            dates,
+            ..Default::default()
+        };
+        let mut ctx = Context::<String>::with_config(&doc, config).unwrap();
+        vec![
+            obo::NaiveDateTime::new(8, 5, 2019, 13, 2).into_owl(&mut ctx),
+            obo::IsoDate::new(2019, 5, 8).into_owl(&mut ctx),
+        ]
+    }
+
+    fn datatype(literal: &str, datatype: &str) -> owl::Literal<String> {
+        owl::Literal::Datatype {
+            literal: literal.to_string(),
+            datatype_iri: owl::Build::new().iri(datatype),
+        }
+    }
+
+    #[test]
+    fn xsd() {
+        assert_eq!(
+            literals(DatePolicy::Xsd),
+            vec![
+                datatype("2019-05-08T13:02:00", xsd::DATETIME),
+                datatype("2019-05-08", xsd::DATE),
+            ]
+        );
+    }
+
+    #[test]
+    fn datetime() {
+        assert_eq!(
+            literals(DatePolicy::DateTime),
+            vec![
+                datatype("2019-05-08T13:02:00", xsd::DATETIME),
+                datatype("2019-05-08T00:00:00", xsd::DATETIME),
+            ]
+        );
+    }
+
+    #[test]
+    fn obo() {
+        assert_eq!(
+            literals(DatePolicy::Obo),
+            vec![
+                owl::Literal::Simple {
+                    literal: String::from("08:05:2019 13:02")
+                },
+                owl::Literal::Simple {
+                    literal: String::from("2019-05-08")
+                },
+            ]
+        );
+    }
+}
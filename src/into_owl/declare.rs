@@ -0,0 +1,112 @@
+use std::collections::BTreeSet;
+
+use horned_owl::model as owl;
+use horned_owl::model::ForIRI;
+use horned_owl::visitor::immutable::Visit;
+use horned_owl::visitor::immutable::Walk;
+
+use crate::constants::uri;
+
+/// Check whether an IRI belongs to the reserved OWL 2 vocabulary.
+///
+/// Entities of the reserved vocabulary (such as `rdfs:label` or
+/// `xsd:string`) are built-in, and must not be declared.
+pub fn is_reserved<A: ForIRI>(iri: &owl::IRI<A>) -> bool {
+    [uri::OWL, uri::RDF, uri::RDFS, uri::XSD]
+        .iter()
+        .any(|ns| iri.starts_with(ns))
+}
+
+/// A visitor recording the declared and the referenced entities.
+#[derive(Debug)]
+struct Collector<A: ForIRI> {
+    declared: BTreeSet<owl::Component<A>>,
+    used: BTreeSet<owl::Component<A>>,
+}
+
+impl<A: ForIRI> Collector<A> {
+    fn use_entity(&mut self, iri: &owl::IRI<A>, declaration: owl::Component<A>) {
+        if !is_reserved(iri) {
+            self.used.insert(declaration);
+        }
+    }
+}
+
+impl<A: ForIRI> Visit<A> for Collector<A> {
+    fn visit_component(&mut self, c: &owl::Component<A>) {
+        match c {
+            owl::Component::DeclareClass(_)
+            | owl::Component::DeclareObjectProperty(_)
+            | owl::Component::DeclareAnnotationProperty(_)
+            | owl::Component::DeclareDataProperty(_)
+            | owl::Component::DeclareNamedIndividual(_)
+            | owl::Component::DeclareDatatype(_) => {
+                self.declared.insert(c.clone());
+            }
+            _ => (),
+        }
+    }
+
+    fn visit_class(&mut self, c: &owl::Class<A>) {
+        self.use_entity(&c.0, owl::DeclareClass(c.clone()).into());
+    }
+
+    fn visit_datatype(&mut self, dt: &owl::Datatype<A>) {
+        self.use_entity(&dt.0, owl::DeclareDatatype(dt.clone()).into());
+    }
+
+    fn visit_object_property(&mut self, op: &owl::ObjectProperty<A>) {
+        self.use_entity(&op.0, owl::DeclareObjectProperty(op.clone()).into());
+    }
+
+    fn visit_data_property(&mut self, dp: &owl::DataProperty<A>) {
+        self.use_entity(&dp.0, owl::DeclareDataProperty(dp.clone()).into());
+    }
+
+    fn visit_annotation_property(&mut self, ap: &owl::AnnotationProperty<A>) {
+        self.use_entity(&ap.0, owl::DeclareAnnotationProperty(ap.clone()).into());
+    }
+
+    fn visit_named_individual(&mut self, i: &owl::NamedIndividual<A>) {
+        self.use_entity(&i.0, owl::DeclareNamedIndividual(i.clone()).into());
+    }
+}
+
+/// A post-processing pass to declare all the entities used in an ontology.
+///
+/// Entities are only declared by the translation when they have their own
+/// frame in the OBO document, but OWL 2 DL requires all the entities to be
+/// declared, including the classes only referenced by an `is_a` clause or
+/// the annotation properties only used in `property_value` clauses.
+/// Entities of the reserved vocabulary are never declared.
+pub struct Declarations<A: ForIRI>(Walk<A, Collector<A>>);
+
+impl<A: ForIRI> Declarations<A> {
+    /// Create a new pass with no component recorded.
+    pub fn new() -> Self {
+        Declarations(Walk::new(Collector {
+            declared: BTreeSet::new(),
+            used: BTreeSet::new(),
+        }))
+    }
+
+    /// Record the entities declared or referenced by a component.
+    pub fn visit(&mut self, component: &owl::AnnotatedComponent<A>) {
+        self.0.annotated_component(component);
+    }
+
+    /// Get the declarations missing from the recorded components.
+    pub fn into_missing(self) -> Vec<owl::AnnotatedComponent<A>> {
+        let Collector { declared, used } = self.0.into_visit();
+        used.into_iter()
+            .filter(|declaration| !declared.contains(declaration))
+            .map(owl::AnnotatedComponent::from)
+            .collect()
+    }
+}
+
+impl<A: ForIRI> Default for Declarations<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
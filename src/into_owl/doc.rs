@@ -1,13 +1,30 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
 use fastobo::ast as obo;
+use fastobo::error::CardinalityError;
 use fastobo::semantics::Identified;
+use horned_owl::model as owl;
 use horned_owl::model::ForIRI;
+use horned_owl::model::HigherKinded;
 use horned_owl::model::MutableOntology;
 
+use super::declare::Declarations;
+use super::profile::Checker;
+use super::roles::is_conflicting;
+use super::roles::Roles;
 use super::Context;
+use super::FrameAxioms;
 use super::IntoOwl;
 use super::IntoOwlCtx;
 use super::IntoOwlPrefixes;
+use crate::config::Config;
+use crate::config::ObsoletionPolicy;
+use crate::config::ProfilePolicy;
+use crate::config::RolePolicy;
+use crate::config::SynonymTypePolicy;
 use crate::error::Error;
+use crate::error::ProfileViolation;
 
 impl IntoOwlPrefixes for obo::OboDoc {
     fn prefixes(&self) -> curie::PrefixMapping {
@@ -22,53 +39,416 @@ impl IntoOwlPrefixes for obo::OboDoc {
 }
 
 impl<A: ForIRI> IntoOwl<A> for obo::OboDoc {
-    fn into_owl<O>(mut self) -> Result<O, Error>
+    fn into_owl<O>(self) -> Result<O, Error>
     where
         O: Default + MutableOntology<A>,
     {
-        // Assign default namespaces to entities missing one.
-        self.assign_namespaces()?; // ignore errors
+        self.into_owl_with_config(Config::default())
+    }
 
-        // Process the xref header macros.
-        self.treat_xrefs();
+    fn into_owl_with_config<O>(self, config: Config) -> Result<O, Error>
+    where
+        O: Default + MutableOntology<A>,
+    {
+        self.into_owl_with_warnings(config).map(|(ont, _)| ont)
+    }
 
-        // Extract conversion context from the document.
-        let mut ctx = Context::from_obodoc(&self)?;
+    fn into_owl_with_warnings<O>(mut self, config: Config) -> Result<(O, Vec<Error>), Error>
+    where
+        O: Default + MutableOntology<A>,
+    {
+        preprocess(&mut self)?;
+        let (mut ctx, frames) = translate(self, config)?;
+        let ont = build(&ctx, components(frames));
+        Ok((ont, std::mem::take(&mut ctx.warnings)))
+    }
+
+    fn into_owl_by_namespace<O>(self) -> Result<BTreeMap<obo::NamespaceIdent, O>, Error>
+    where
+        O: Default + MutableOntology<A>,
+    {
+        self.into_owl_by_namespace_with_config(Config::default())
+    }
 
-        // Create the output ontology
-        let mut ont = O::default();
+    fn into_owl_by_namespace_with_config<O>(
+        mut self,
+        config: Config,
+    ) -> Result<BTreeMap<obo::NamespaceIdent, O>, Error>
+    where
+        O: Default + MutableOntology<A>,
+    {
+        preprocess(&mut self)?;
 
-        // Convert the header frame: most frames end up as Ontology annotations,
-        // but some of them require extra axioms.
-        let header = std::mem::take(self.header_mut());
-        for axiom in header.into_owl(&mut ctx).into_iter() {
-            ont.insert(axiom);
+        // Record the namespace of terms and instances before translation,
+        // since typedefs are shared by all modules.
+        let mut namespaces = HashMap::new();
+        for entity in self.entities() {
+            let ns = match entity {
+                obo::EntityFrame::Term(frame) => {
+                    frame.iter().find_map(|line| match line.as_inner() {
+                        obo::TermClause::Namespace(ns) => Some(ns.as_ref().clone()),
+                        _ => None,
+                    })
+                }
+                obo::EntityFrame::Instance(frame) => {
+                    frame.iter().find_map(|line| match line.as_inner() {
+                        obo::InstanceClause::Namespace(ns) => Some(ns.as_ref().clone()),
+                        _ => None,
+                    })
+                }
+                obo::EntityFrame::Typedef(_) => continue,
+            };
+            let ns = ns.ok_or_else(|| CardinalityError::missing("namespace"))?;
+            namespaces.insert(entity.as_id().clone(), ns);
         }
 
-        // NOTE: force import of the oboInOwl ontology?
-        // let iri = "http://www.geneontology.org/formats/oboInOwl";
-        // ont.insert(Component::Import(Import(ctx.build.iri(iri))));
+        // Translate the document, and group the axioms by namespace.
+        let (ctx, frames) = translate(self, config)?;
+        let mut header = Vec::new();
+        let mut shared = Vec::new();
+        let mut modules: BTreeMap<obo::NamespaceIdent, Vec<_>> = BTreeMap::new();
+        for (id, axioms) in frames.into_iter() {
+            let axioms = axioms.into_iter().map(|(axiom, _)| axiom);
+            match id.map(|id| namespaces.get(&id)) {
+                None => header.extend(axioms),
+                Some(None) => shared.extend(axioms),
+                Some(Some(ns)) => modules.entry(ns.clone()).or_default().extend(axioms),
+            }
+        }
 
-        // Convert each entity to a set of OWL axioms that are then added to the ontology.
-        let entities = std::mem::replace(self.entities_mut(), Default::default());
-        for entity in entities.into_iter() {
-            ctx.current_frame = entity.as_id().clone().into_owl(&mut ctx);
-            match entity {
-                obo::EntityFrame::Term(frame) => {
-                    for axiom in frame.into_owl(&mut ctx) {
-                        ont.insert(axiom);
+        // Build one ontology per namespace, with a derived ontology ID.
+        let mut onts = BTreeMap::new();
+        for (ns, axioms) in modules.into_iter() {
+            let header = header.iter().map(|axiom| match &axiom.component {
+                owl::Component::OntologyID(id) => owl::AnnotatedComponent::from(owl::OntologyID {
+                    iri: id.iri.as_ref().map(|iri| module_iri(&ctx, iri, &ns)),
+                    viri: id.viri.as_ref().map(|viri| module_iri(&ctx, viri, &ns)),
+                }),
+                _ => axiom.clone(),
+            });
+            let ont = build(&ctx, header.chain(shared.iter().cloned()).chain(axioms));
+            onts.insert(ns, ont);
+        }
+
+        Ok(onts)
+    }
+
+    fn into_owl_subset<O>(self, subsets: &[obo::SubsetIdent], closure: bool) -> Result<O, Error>
+    where
+        O: Default + MutableOntology<A>,
+    {
+        self.into_owl_subset_with_config(subsets, closure, Config::default())
+    }
+
+    fn into_owl_subset_with_config<O>(
+        mut self,
+        subsets: &[obo::SubsetIdent],
+        closure: bool,
+        config: Config,
+    ) -> Result<O, Error>
+    where
+        O: Default + MutableOntology<A>,
+    {
+        // NB: the xref header macros may add `is_a` clauses used by the closure.
+        preprocess(&mut self)?;
+
+        // Remove terms outside of the subsets before translation.
+        super::subset::extract_subset(&mut self, subsets, closure);
+        let (ctx, frames) = translate(self, config)?;
+        Ok(build(&ctx, components(frames)))
+    }
+}
+
+/// The axioms produced from each frame, with the identifier of entity frames.
+type Frames<A> = Vec<(Option<obo::Ident>, FrameAxioms<A>)>;
+
+/// Iterate over the axioms produced from each frame, without their clause.
+fn components<A: ForIRI>(frames: Frames<A>) -> impl Iterator<Item = owl::AnnotatedComponent<A>> {
+    frames
+        .into_iter()
+        .flat_map(|(_, axioms)| axioms.into_iter().map(|(axiom, _)| axiom))
+}
+
+/// Process the header macros of a document before translation.
+fn preprocess(doc: &mut obo::OboDoc) -> Result<(), Error> {
+    // Assign default namespaces to entities missing one.
+    doc.assign_namespaces()?;
+
+    // Process the xref header macros.
+    doc.treat_xrefs();
+    Ok(())
+}
+
+/// Translate a preprocessed document to OWL axioms grouped by frame.
+///
+/// The axioms of the header frame come first, without a frame identifier,
+/// followed by the axioms of each translated entity frame.
+fn translate<A: ForIRI>(
+    mut doc: obo::OboDoc,
+    config: Config,
+) -> Result<(Context<A>, Frames<A>), Error> {
+    // Extract conversion context from the document.
+    let mut ctx = Context::with_config(&doc, config)?;
+
+    // Check the term and typedef frames can be translated.
+    for frame in doc.entities().iter().flat_map(obo::EntityFrame::as_term) {
+        super::term::validate(frame)?;
+    }
+    for frame in doc.entities().iter().flat_map(obo::EntityFrame::as_typedef) {
+        super::typedef::validate(frame)?;
+    }
+
+    // Check the replacements of obsolete terms if they are going to be
+    // handled differently.
+    if ctx.config.obsoletion != ObsoletionPolicy::Keep {
+        super::term::check_replacements(&doc)?;
+    }
+
+    // Check the synonyms against the declared synonym types: fail on the
+    // first inconsistency if requested, or report them as warnings unless
+    // they are repaired during the translation.
+    let synonyms = super::syn::check_synonyms(&doc, &ctx);
+    match ctx.config.synonym_types {
+        SynonymTypePolicy::Error => {
+            if let Some(error) = synonyms.into_iter().next() {
+                return Err(error);
+            }
+        }
+        SynonymTypePolicy::Ignore => ctx.warnings.extend(synonyms),
+        SynonymTypePolicy::Repair => (),
+    }
+
+    // Parse the `owl-axioms` header clauses, failing if they are invalid.
+    ctx.owl_axioms = super::header::read_owl_axioms(doc.header(), &ctx.build)?;
+
+    // Check property value literals before conversion, and only fail on
+    // invalid literals if requested.
+    let invalid = super::pv::check_literals(&doc, &mut ctx);
+    if !ctx.config.strict_literals {
+        ctx.warnings.extend(invalid);
+    } else if let Some(error) = invalid.into_iter().next() {
+        return Err(error);
+    }
+
+    // Convert the header frame: most frames end up as Ontology annotations,
+    // but some of them require extra axioms.
+    let header = std::mem::take(doc.header_mut());
+    let mut frames = vec![(None, header.into_owl(&mut ctx))];
+
+    // NOTE: force import of the oboInOwl ontology?
+    // let iri = "http://www.geneontology.org/formats/oboInOwl";
+    // ont.insert(Component::Import(Import(ctx.build.iri(iri))));
+
+    // Convert each entity to a set of OWL axioms, recording the frame
+    // they originate from.
+    let entities = std::mem::take(doc.entities_mut());
+    for entity in entities.into_iter() {
+        let id = entity.as_id().clone();
+        ctx.current_frame = id.clone().into_owl(&mut ctx);
+        let axioms = match entity {
+            obo::EntityFrame::Term(frame) => frame.into_owl(&mut ctx),
+            obo::EntityFrame::Typedef(frame) => frame.into_owl(&mut ctx),
+            obo::EntityFrame::Instance(frame) if ctx.config.data_properties => {
+                frame.into_owl(&mut ctx)
+            }
+            _ => continue, // NB: individuals are ignored
+        };
+        frames.push((Some(id), axioms));
+    }
+
+    // Check for IRIs used in conflicting roles if needed.
+    if ctx.config.role_conflicts != RolePolicy::Ignore {
+        let mut roles = Roles::new();
+        for (id, axioms) in frames.iter() {
+            for (axiom, _) in axioms.iter() {
+                roles.visit(id.as_ref(), axiom);
+            }
+        }
+        if ctx.config.role_conflicts == RolePolicy::Error {
+            let conflicts = roles.conflicts();
+            if !conflicts.is_empty() {
+                return Err(Error::RoleConflicts(conflicts));
+            }
+        } else {
+            let conflicting = roles.conflicting_uses();
+            for (_, axioms) in frames.iter_mut() {
+                axioms.retain(|(axiom, _)| !is_conflicting(&conflicting, axiom));
+            }
+        }
+    }
+
+    // Check the axioms against the requested OWL 2 profile if needed.
+    if let Some(profile) = ctx.config.profile {
+        let all = frames
+            .iter()
+            .flat_map(|(_, axioms)| axioms.iter().map(|(axiom, _)| axiom));
+        let checker = Checker::new(profile, all);
+        let mut violations = Vec::new();
+        for (id, axioms) in frames.iter_mut() {
+            match ctx.config.profile_policy {
+                ProfilePolicy::Error => {
+                    for (axiom, tag) in axioms.iter() {
+                        if let Err(reason) = checker.check(axiom) {
+                            violations.push(ProfileViolation {
+                                frame: id.clone(),
+                                clause: tag.map(String::from),
+                                reason,
+                            });
+                        }
                     }
                 }
-                obo::EntityFrame::Typedef(frame) => {
-                    for axiom in frame.into_owl(&mut ctx) {
-                        ont.insert(axiom);
+                ProfilePolicy::Drop => axioms.retain(|(axiom, _)| checker.check(axiom).is_ok()),
+                ProfilePolicy::Approximate => {
+                    for (axiom, tag) in std::mem::take(axioms) {
+                        for approximated in checker.approximate(axiom) {
+                            axioms.push((approximated, tag));
+                        }
                     }
                 }
-                _ => (), // NB: individuals are ignored
-            };
+            }
+        }
+        if !violations.is_empty() {
+            return Err(Error::ProfileViolations(violations));
         }
+    }
+
+    // Annotate the axioms with their provenance if needed.
+    if let Some(property) = &ctx.config.provenance {
+        let ap = owl::AnnotationProperty(ctx.build.iri(property.as_str()));
+        for (id, axioms) in frames.iter_mut() {
+            // NB: ontology annotations can not be annotated themselves.
+            let annotable = axioms.iter_mut().filter(|(axiom, _)| {
+                axiom.is_axiom()
+                    && !matches!(axiom.component, owl::Component::OntologyAnnotation(_))
+            });
+            for (axiom, tag) in annotable {
+                let literal = match (id.as_ref(), tag) {
+                    (Some(id), Some(tag)) => format!("{} {}", id, tag),
+                    (Some(id), None) => id.to_string(),
+                    (None, Some(tag)) => tag.to_string(),
+                    (None, None) => continue,
+                };
+                axiom.ann.insert(owl::Annotation {
+                    ap: ap.clone(),
+                    av: owl::AnnotationValue::Literal(owl::Literal::Simple { literal }),
+                });
+            }
+        }
+    }
+
+    Ok((ctx, frames))
+}
+
+/// Create an ontology with the given axioms, declaring entities if needed.
+fn build<A, O, I>(ctx: &Context<A>, axioms: I) -> O
+where
+    A: ForIRI,
+    O: Default + MutableOntology<A>,
+    I: IntoIterator<Item = owl::AnnotatedComponent<A>>,
+{
+    // Create the output ontology, recording the entities to declare.
+    let mut ont = O::default();
+    let mut declarations = Declarations::new();
+    for axiom in axioms {
+        if ctx.config.declare_entities {
+            declarations.visit(&axiom);
+        }
+        ont.insert(axiom);
+    }
+
+    // Declare the entities that are used without being declared.
+    if ctx.config.declare_entities {
+        for axiom in declarations.into_missing() {
+            ont.insert(axiom);
+        }
+    }
+
+    ont
+}
+
+/// Derive the IRI of a namespace module from the IRI of the whole ontology.
+fn module_iri<A: ForIRI>(
+    ctx: &Context<A>,
+    iri: &owl::IRI<A>,
+    ns: &obo::NamespaceIdent,
+) -> owl::IRI<A> {
+    let base = iri.as_ref();
+    let base = base.strip_suffix(".owl").unwrap_or(base);
+    ctx.build.iri(format!("{}/{}.owl", base, ns))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use horned_owl::ontology::set::SetOntology;
+
+    use super::*;
+    use crate::constants::property::rdfs;
+    use crate::error::FrameError;
+
+    #[test]
+    fn provenance_of_identical_axioms() {
+        let doc = fastobo::from_str(
+            "ontology: test\ndefault-namespace: test\n\n[Term]\nid: TST:001\ncomment: a comment\nproperty_value: rdfs:comment \"a comment\" xsd:string\n",
+        )
+        .unwrap();
+        let config = Config {
+            provenance: Some(String::from("http://example.com/provenance")),
+            ..Config::default()
+        };
+        let ont: SetOntology<String> = doc.into_owl_with_config(config).unwrap();
+
+        let mut provenance = ont
+            .iter()
+            .filter(|ac| match &ac.component {
+                owl::Component::AnnotationAssertion(a) => a.ann.ap.0.as_ref() == rdfs::COMMENT,
+                _ => false,
+            })
+            .flat_map(|ac| ac.ann.iter())
+            .map(|ann| match &ann.av {
+                owl::AnnotationValue::Literal(l) => l.literal().clone(),
+                other => panic!("unexpected provenance: {:?}", other),
+            })
+            .collect::<Vec<_>>();
+        provenance.sort();
+        assert_eq!(
+            provenance,
+            vec!["TST:001 comment", "TST:001 property_value"]
+        );
+    }
+
+    #[test]
+    fn synonym_warnings() {
+        let doc = fastobo::from_str(
+            "ontology: test\ndefault-namespace: test\nsynonymtypedef: systematic \"Systematic synonym\" EXACT\n\n[Term]\nid: TST:001\nsynonym: \"x\" BROAD systematic []\nsynonym: \"y\" EXACT unknown []\n",
+        )
+        .unwrap();
+
+        let (_, warnings) = doc
+            .clone()
+            .into_owl_with_warnings::<SetOntology<String>>(Config::default())
+            .unwrap();
+        assert!(matches!(
+            warnings.as_slice(),
+            [
+                Error::InvalidFrame {
+                    error: FrameError::SynonymScopeMismatch { .. },
+                    ..
+                },
+                Error::InvalidFrame {
+                    error: FrameError::UndeclaredSynonymType { .. },
+                    ..
+                },
+            ]
+        ));
 
-        // Return the produced OWL ontology.
-        Ok(ont)
+        let config = Config {
+            synonym_types: SynonymTypePolicy::Repair,
+            ..Config::default()
+        };
+        let (_, warnings) = doc
+            .into_owl_with_warnings::<SetOntology<String>>(config)
+            .unwrap();
+        assert!(warnings.is_empty());
     }
 }
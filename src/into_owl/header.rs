@@ -1,14 +1,61 @@
 use fastobo::ast as obo;
 use horned_owl::model as owl;
 use horned_owl::model::AnnotatedComponent;
+use horned_owl::model::Build;
 use horned_owl::model::ForIRI;
 use horned_owl::model::HigherKinded;
 use horned_owl::model::OntologyID;
+use horned_owl::ontology::set::SetOntology;
 
+use super::clause_tag;
 use super::Context;
+use super::FrameAxioms;
 use super::IntoOwlCtx;
+use crate::constants::license;
 use crate::constants::property;
-use crate::constants::uri;
+use crate::error::Error;
+
+/// The identifiers of the known licenses, with their canonical IRI.
+const LICENSE_NAMES: &[(&str, &str)] = &[
+    ("cc-by-3.0", license::CC_BY_3_0),
+    ("cc-by 3.0", license::CC_BY_3_0),
+    ("cc by 3.0", license::CC_BY_3_0),
+    ("cc-by-4.0", license::CC_BY_4_0),
+    ("cc-by 4.0", license::CC_BY_4_0),
+    ("cc by 4.0", license::CC_BY_4_0),
+    ("cc0", license::CC0_1_0),
+    ("cc0-1.0", license::CC0_1_0),
+    ("cc0 1.0", license::CC0_1_0),
+];
+
+/// Get the normalized IRI of a license from a `dcterms:license` value.
+///
+/// The SPDX identifiers and common names of the Creative Commons licenses
+/// (such as `CC-BY-4.0` or `CC BY 4.0`) are resolved to their canonical IRI,
+/// and the URLs of these licenses are normalized to use `https` and a
+/// trailing slash. `None` is returned for any other value, which is then
+/// kept as-is.
+fn license_iri(value: &str) -> Option<String> {
+    let lower = value.trim().to_ascii_lowercase();
+
+    // Resolve the known license identifiers.
+    if let Some((_, iri)) = LICENSE_NAMES.iter().find(|(name, _)| *name == lower) {
+        return Some(iri.to_string());
+    }
+
+    // Normalize the URLs of the known licenses.
+    let rest = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let path = rest.trim_end_matches('/');
+    let path = path.strip_suffix("/legalcode").unwrap_or(path);
+    let url = format!("https://{}/", path);
+    [license::CC_BY_3_0, license::CC_BY_4_0, license::CC0_1_0]
+        .iter()
+        .find(|iri| **iri == url)
+        .map(|iri| iri.to_string())
+}
 
 impl<A: ForIRI> IntoOwlCtx<A> for obo::HeaderClause {
     type Owl = Vec<owl::AnnotatedComponent<A>>;
@@ -24,12 +71,16 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::HeaderClause {
                 }),
             )],
 
-            // no equivalent
-            // --> should be added as the Ontology IRI
-            obo::HeaderClause::DataVersion(_) => Vec::new(),
+            // `owl:versionInfo` annotation
+            // --> also used to build the Version IRI of the ontology
+            obo::HeaderClause::DataVersion(v) => vec![owl::AnnotatedComponent::from(
+                owl::OntologyAnnotation(owl::Annotation {
+                    ap: ctx.build.annotation_property(property::owl::VERSION_INFO),
+                    av: v.into_owl(ctx).into(),
+                }),
+            )],
 
-            // `oboInOwl:hasDate` annotation
-            // --> QUESTION: should the datatype_iri be `dateTime` or `string` ?
+            // `oboInOwl:hasDate` annotation, rendered according to `Config::dates`
             obo::HeaderClause::Date(dt) => vec![owl::AnnotatedComponent::from(
                 owl::OntologyAnnotation(owl::Annotation {
                     ap: ctx
@@ -72,33 +123,36 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::HeaderClause {
             //         <rdfs:comment rdf:datatype="xsd:string">T(description)</rdfs:comment>
             //         <rdfs:subPropertyOf rdf:resource="http://www.geneontology.org/formats/oboInOwl#SubsetProperty"/>
             //     </owl:AnnotationProperty>
-            obo::HeaderClause::Subsetdef(subset, desc) => vec![
-                owl::AnnotatedComponent::from(owl::DeclareAnnotationProperty(
-                    owl::AnnotationProperty::from(subset.into_owl(ctx)),
-                )),
-                owl::AnnotatedComponent::from(owl::SubAnnotationPropertyOf {
-                    sub: owl::AnnotationProperty::from(subset.into_owl(ctx)),
-                    sup: owl::AnnotationProperty::from(
-                        ctx.build.iri(property::obo_in_owl::SUBSET_PROPERTY),
-                    ),
-                }),
-                owl::AnnotatedComponent::from(owl::AnnotationAssertion {
-                    subject: owl::AnnotationSubject::from(subset.into_owl(ctx)),
-                    ann: owl::Annotation {
-                        ap: ctx.build.annotation_property(property::rdfs::LABEL),
-                        av: owl::AnnotationValue::Literal(owl::Literal::Simple {
-                            literal: subset.to_string(),
-                        }),
-                    },
-                }),
-                owl::AnnotatedComponent::from(owl::AnnotationAssertion {
-                    subject: owl::AnnotationSubject::from(subset.into_owl(ctx)),
-                    ann: owl::Annotation {
-                        ap: ctx.build.annotation_property(property::rdfs::COMMENT),
-                        av: owl::AnnotationValue::Literal(desc.into_owl(ctx)),
-                    },
-                }),
-            ],
+            obo::HeaderClause::Subsetdef(subset, desc) => {
+                let iri: owl::IRI<A> = subset.into_owl(ctx);
+                vec![
+                    owl::AnnotatedComponent::from(owl::DeclareAnnotationProperty(
+                        owl::AnnotationProperty::from(iri.clone()),
+                    )),
+                    owl::AnnotatedComponent::from(owl::SubAnnotationPropertyOf {
+                        sub: owl::AnnotationProperty::from(iri.clone()),
+                        sup: owl::AnnotationProperty::from(
+                            ctx.build.iri(property::obo_in_owl::SUBSET_PROPERTY),
+                        ),
+                    }),
+                    owl::AnnotatedComponent::from(owl::AnnotationAssertion {
+                        subject: owl::AnnotationSubject::from(&iri),
+                        ann: owl::Annotation {
+                            ap: ctx.build.annotation_property(property::rdfs::LABEL),
+                            av: owl::AnnotationValue::Literal(owl::Literal::Simple {
+                                literal: subset.to_string(),
+                            }),
+                        },
+                    }),
+                    owl::AnnotatedComponent::from(owl::AnnotationAssertion {
+                        subject: owl::AnnotationSubject::from(&iri),
+                        ann: owl::Annotation {
+                            ap: ctx.build.annotation_property(property::rdfs::COMMENT),
+                            av: owl::AnnotationValue::Literal(desc.into_owl(ctx)),
+                        },
+                    }),
+                ]
+            }
 
             // `owl:AnnotationProperty`
             //      <owl:AnnotationProperty rdf:about="http://purl.obolibrary.org/obo/go#systematic_synonym">
@@ -107,18 +161,19 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::HeaderClause {
             //          <rdfs:subPropertyOf rdf:resource="http://www.geneontology.org/formats/oboInOwl#SynonymTypeProperty"/>
             //      </owl:AnnotationProperty>
             obo::HeaderClause::SynonymTypedef(ty, desc, optscope) => {
+                let iri: owl::IRI<A> = ty.into_owl(ctx);
                 let mut axioms = vec![
                     owl::AnnotatedComponent::from(owl::DeclareAnnotationProperty(
-                        owl::AnnotationProperty::from(ty.into_owl(ctx)),
+                        owl::AnnotationProperty::from(iri.clone()),
                     )),
                     owl::AnnotatedComponent::from(owl::SubAnnotationPropertyOf {
-                        sub: owl::AnnotationProperty::from(ty.into_owl(ctx)),
+                        sub: owl::AnnotationProperty::from(iri.clone()),
                         sup: owl::AnnotationProperty::from(
                             ctx.build.iri(property::obo_in_owl::SYNONYM_TYPE_PROPERTY),
                         ),
                     }),
                     owl::AnnotatedComponent::from(owl::AnnotationAssertion {
-                        subject: owl::AnnotationSubject::from(ty.into_owl(ctx)),
+                        subject: owl::AnnotationSubject::from(&iri),
                         ann: owl::Annotation {
                             ap: ctx.build.annotation_property(property::rdfs::LABEL),
                             av: owl::AnnotationValue::Literal(desc.into_owl(ctx)),
@@ -127,7 +182,7 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::HeaderClause {
                 ];
                 if let Some(scope) = optscope {
                     axioms.push(owl::AnnotatedComponent::from(owl::AnnotationAssertion {
-                        subject: owl::AnnotationSubject::from(ty.into_owl(ctx)),
+                        subject: owl::AnnotationSubject::from(&iri),
                         ann: owl::Annotation {
                             ap: ctx
                                 .build
@@ -182,10 +237,21 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::HeaderClause {
                 }),
             )],
 
-            // translate as an annotation
-            obo::HeaderClause::PropertyValue(pv) => vec![owl::AnnotatedComponent::from(
-                owl::OntologyAnnotation(pv.into_owl(ctx)),
-            )],
+            // translate as an annotation, normalizing the license if any
+            obo::HeaderClause::PropertyValue(pv) => {
+                let mut ann = pv.into_owl(ctx);
+                if ann.ap.0.as_ref() == property::dcterms::LICENSE {
+                    let value = match &ann.av {
+                        owl::AnnotationValue::IRI(iri) => Some(iri.as_ref()),
+                        owl::AnnotationValue::Literal(l) => Some(l.literal().as_str()),
+                        _ => None,
+                    };
+                    if let Some(iri) = value.and_then(license_iri) {
+                        ann.av = owl::AnnotationValue::IRI(ctx.build.iri(iri));
+                    }
+                }
+                vec![owl::AnnotatedComponent::from(owl::OntologyAnnotation(ann))]
+            }
 
             // no actual OWL equivalent, but exposed as the Ontology IRI
             // when creating the conversion context.
@@ -200,47 +266,58 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::HeaderClause {
     }
 }
 
+/// Parse the content of `owl-axioms` clauses as OWL functional syntax.
+/// Parse the `owl-axioms` clauses of a header frame.
+///
+/// The clauses are joined and parsed as a single OWL functional syntax
+/// document, and the logical and annotation axioms it declares are returned.
+pub fn read_owl_axioms<A: ForIRI>(
+    frame: &obo::HeaderFrame,
+    build: &Build<A>,
+) -> Result<Vec<AnnotatedComponent<A>>, Error> {
+    let owl_axioms = frame
+        .iter()
+        .filter_map(|clause| match clause {
+            obo::HeaderClause::OwlAxioms(s) => Some(s.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if owl_axioms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let reader = std::io::BufReader::new(std::io::Cursor::new(owl_axioms.join("\n")));
+    let (ont, _): (SetOntology<A>, _) = horned_owl::io::ofn::reader::read_with_build(reader, build)
+        .map_err(|e| Error::InvalidOwlAxioms(e.to_string()))?;
+    Ok(ont.into_iter().filter(|c| !c.is_meta()).collect())
+}
+
 impl<A: ForIRI> IntoOwlCtx<A> for obo::HeaderFrame {
-    type Owl = Vec<owl::AnnotatedComponent<A>>;
+    type Owl = FrameAxioms<A>;
     fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
-        let mut owl_axioms: Vec<String> = Vec::new();
-        let mut axioms: Vec<owl::AnnotatedComponent<A>> = Vec::with_capacity(self.len());
+        let mut axioms: Self::Owl = Vec::with_capacity(self.len());
 
         // declare the IRI and Version IRI for the ontology.
-        if let Ok(name) = self.ontology() {
-            let oid = OntologyID {
-                iri: Some(ctx.build.iri(format!("{}{}.owl", uri::OBO, name))),
-                viri: self
-                    .data_version()
-                    .map(|dv| {
-                        ctx.build
-                            .iri(format!("{}{}/{}/{}.owl", uri::OBO, name, dv, name))
-                    })
-                    .ok(),
-            };
-            axioms.push(AnnotatedComponent::from(oid));
-        } else {
-            axioms.push(AnnotatedComponent::from(OntologyID::default()));
-        }
+        let oid = OntologyID {
+            iri: Some(ctx.build.iri(ctx.ontology_iri.as_str())),
+            viri: self
+                .data_version()
+                .map(|dv| ctx.version_iri(dv.as_str()))
+                .ok(),
+        };
+        axioms.push((AnnotatedComponent::from(oid), None));
 
         // Process the header frame clauses
         for clause in self.into_iter() {
-            if let obo::HeaderClause::OwlAxioms(s) = clause {
-                owl_axioms.push(s.into_string());
-            } else {
-                axioms.append(&mut clause.into_owl(ctx))
-            }
+            let tag = clause_tag(&clause);
+            axioms.extend(clause.into_owl(ctx).into_iter().map(|ac| (ac, tag)));
         }
 
         // FIXME: https://github.com/owlcollab/oboformat/issues/116
-        // Parse the remaining axioms in `owl-axioms` clauses.
-        if !owl_axioms.is_empty() {
-            let text = owl_axioms.join("\n");
-            let reader = std::io::BufReader::new(std::io::Cursor::new(&text));
-            let (ont, _) = horned_owl::io::ofn::reader::read_with_build(reader, &ctx.build)
-                .expect("invalid functional ontology");
-            axioms.extend(ont.into_iter().filter(|c| !c.is_meta()));
-        }
+        // Add the axioms of the `owl-axioms` clauses, which were parsed
+        // with `read_owl_axioms` before the translation.
+        let owl_axioms = std::mem::take(&mut ctx.owl_axioms);
+        axioms.extend(owl_axioms.into_iter().map(|ac| (ac, None)));
 
         axioms
     }
@@ -260,3 +337,47 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::Import {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn license_iri_names() {
+        assert_eq!(
+            license_iri("CC-BY 4.0").as_deref(),
+            Some(license::CC_BY_4_0)
+        );
+        assert_eq!(
+            license_iri("CC BY 3.0").as_deref(),
+            Some(license::CC_BY_3_0)
+        );
+        assert_eq!(
+            license_iri("cc-by-4.0").as_deref(),
+            Some(license::CC_BY_4_0)
+        );
+        assert_eq!(license_iri("CC0").as_deref(), Some(license::CC0_1_0));
+        assert_eq!(license_iri("CC-BY"), None);
+        assert_eq!(license_iri("CC-BY-SA 4.0"), None);
+        assert_eq!(license_iri("all rights reserved"), None);
+    }
+
+    #[test]
+    fn license_iri_urls() {
+        assert_eq!(
+            license_iri("http://creativecommons.org/licenses/by/4.0").as_deref(),
+            Some(license::CC_BY_4_0)
+        );
+        assert_eq!(
+            license_iri("https://www.creativecommons.org/publicdomain/zero/1.0/legalcode")
+                .as_deref(),
+            Some(license::CC0_1_0)
+        );
+        assert_eq!(license_iri("https://opensource.org/licenses/MIT"), None);
+        assert_eq!(
+            license_iri("http://creativecommons.org/licenses/by-nc/4.0/"),
+            None
+        );
+    }
+}
@@ -27,7 +27,7 @@ impl<A: ForIRI> IntoOwlCtx<A> for &obo::UnprefixedIdent {
     type Owl = owl::IRI<A>;
     fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
         ctx.build
-            .iri(format!("{}#{}", &ctx.ontology_iri, self.as_str()))
+            .iri(format!("{}#{}", ctx.ontology_base(), self.as_str()))
     }
 }
 
@@ -40,14 +40,26 @@ impl<A: ForIRI> IntoOwlCtx<A> for &obo::Url {
 }
 
 /// Convert an arbitrary OBO identifier to an OWL IRI.
+///
+/// Identifiers of anonymous frames are converted to their skolem IRI, and
+/// other identifiers are only expanded once, then retrieved from the cache
+/// of the context.
 impl<A: ForIRI> IntoOwlCtx<A> for &obo::Ident {
     type Owl = owl::IRI<A>;
     fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
-        match self {
+        if let Some(iri) = ctx.anonymous.get(self) {
+            return iri.clone();
+        }
+        if let Some(iri) = ctx.iris.get(self) {
+            return iri.clone();
+        }
+        let iri = match self {
             obo::Ident::Url(url) => url.into_owl(ctx),
             obo::Ident::Unprefixed(id) => id.into_owl(ctx),
             obo::Ident::Prefixed(id) => id.into_owl(ctx),
-        }
+        };
+        ctx.iris.insert(self.clone(), iri.clone());
+        iri
     }
 }
 
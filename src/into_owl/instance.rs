@@ -0,0 +1,60 @@
+use fastobo::ast as obo;
+use horned_owl::model as owl;
+use horned_owl::model::ForIRI;
+
+use super::Context;
+use super::FrameAxioms;
+use super::IntoOwlCtx;
+
+/// Convert an instance frame to data property assertions.
+///
+/// Instance frames are otherwise not translated: this only handles the
+/// `property_value` clauses using typedefs translated as data properties
+/// (see [`Config::data_properties`](../../struct.Config.html#structfield.data_properties)).
+/// Anonymous instances are translated to OWL anonymous individuals.
+impl<A: ForIRI> IntoOwlCtx<A> for obo::InstanceFrame {
+    type Owl = FrameAxioms<A>;
+    fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
+        // The translated axioms, with the tag of their clause.
+        let mut axioms: Self::Owl = Vec::new();
+
+        // Anonymous instances are translated to anonymous individuals.
+        let anonymous = ctx.is_anonymous(self.id().as_inner().as_ref());
+        let individual = if anonymous {
+            owl::Individual::Anonymous(ctx.build.anon(self.id().as_inner().to_string()))
+        } else {
+            owl::Individual::Named(owl::NamedIndividual::from(&ctx.current_frame))
+        };
+
+        // Convert literal property values of data properties.
+        for line in self.into_iter() {
+            let comment = ctx.line_comment(&line);
+            if let obo::InstanceClause::PropertyValue(pv) = line.into_inner() {
+                let ann = pv.into_owl(ctx);
+                if let owl::AnnotationValue::Literal(to) = ann.av {
+                    if ctx.is_data_property(&ann.ap.0) {
+                        let ac = owl::AnnotatedComponent::new(
+                            owl::DataPropertyAssertion {
+                                dp: owl::DataProperty(ann.ap.0),
+                                from: individual.clone(),
+                                to,
+                            },
+                            comment,
+                        );
+                        axioms.push((ac, Some("property_value")));
+                    }
+                }
+            }
+        }
+
+        // Declare the individual if it was used in an assertion.
+        if !axioms.is_empty() && !anonymous {
+            let declaration = owl::AnnotatedComponent::from(owl::DeclareNamedIndividual(
+                owl::NamedIndividual::from(&ctx.current_frame),
+            ));
+            axioms.push((declaration, None));
+        }
+
+        axioms
+    }
+}
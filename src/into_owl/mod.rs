@@ -1,16 +1,23 @@
 mod date;
+mod declare;
 mod def;
 mod doc;
 mod header;
 mod id;
+mod instance;
+mod profile;
 mod pv;
 mod qualifier;
+mod roles;
 mod strings;
+mod subset;
 mod syn;
 mod term;
 mod typedef;
 mod xref;
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::convert::TryFrom;
@@ -18,15 +25,102 @@ use std::ops::Deref;
 
 use fastobo::ast as obo;
 use fastobo::error::CardinalityError;
+use fastobo::semantics::Identified;
+use fastobo::semantics::OboClause;
 use horned_owl::model as owl;
 use horned_owl::model::ForIRI;
 use horned_owl::model::MutableOntology;
 
+use crate::config::Config;
+use crate::constants::property;
 use crate::constants::uri;
 use crate::error::Error;
 
 // ---------------------------------------------------------------------------
 
+/// The axioms translated from a frame, with the tag of their OBO clause.
+///
+/// The tag is `None` for the axioms that do not originate from a single
+/// clause, such as the declaration of the frame entity.
+pub type FrameAxioms<A> = Vec<(owl::AnnotatedComponent<A>, Option<&'static str>)>;
+
+/// The tags of the OBO 1.4 clauses that can be translated to axioms.
+const CLAUSE_TAGS: &[&str] = &[
+    // header clauses
+    "format-version",
+    "data-version",
+    "date",
+    "saved-by",
+    "auto-generated-by",
+    "import",
+    "subsetdef",
+    "synonymtypedef",
+    "default-namespace",
+    "namespace-id-rule",
+    "idspace",
+    "treat-xrefs-as-equivalent",
+    "treat-xrefs-as-genus-differentia",
+    "treat-xrefs-as-reverse-genus-differentia",
+    "treat-xrefs-as-relationship",
+    "treat-xrefs-as-is_a",
+    "treat-xrefs-as-has-subclass",
+    "remark",
+    "ontology",
+    "owl-axioms",
+    // entity clauses
+    "is_anonymous",
+    "name",
+    "namespace",
+    "alt_id",
+    "def",
+    "comment",
+    "subset",
+    "synonym",
+    "xref",
+    "builtin",
+    "property_value",
+    "is_a",
+    "intersection_of",
+    "union_of",
+    "equivalent_to",
+    "disjoint_from",
+    "relationship",
+    "created_by",
+    "creation_date",
+    "is_obsolete",
+    "replaced_by",
+    "consider",
+    "instance_of",
+    "domain",
+    "range",
+    "holds_over_chain",
+    "is_anti_symmetric",
+    "is_cyclic",
+    "is_reflexive",
+    "is_symmetric",
+    "is_asymmetric",
+    "is_transitive",
+    "is_functional",
+    "is_inverse_functional",
+    "inverse_of",
+    "transitive_over",
+    "equivalent_to_chain",
+    "disjoint_over",
+    "expand_assertion_to",
+    "expand_expression_to",
+    "is_metadata_tag",
+    "is_class_level",
+];
+
+/// Get the tag of a clause, if it is one of the OBO 1.4 clauses.
+///
+/// Unreserved header clauses have a tag only known at runtime, but are not
+/// translated to any axiom anyway.
+pub fn clause_tag<C: OboClause>(clause: &C) -> Option<&'static str> {
+    let tag = clause.tag();
+    CLAUSE_TAGS.iter().copied().find(|known| *known == tag)
+}
+
 /// The internal trait for data conversion;
 ///
 /// This is not exposed because `ctx` can be mostly inferred from the source
@@ -55,6 +149,71 @@ pub trait IntoOwl<A: ForIRI> {
     fn into_owl<O>(self) -> Result<O, Error>
     where
         O: Default + MutableOntology<A>;
+
+    /// Convert the OBO document into an `Ontology` using the given options.
+    fn into_owl_with_config<O>(self, config: Config) -> Result<O, Error>
+    where
+        O: Default + MutableOntology<A>;
+
+    /// Convert the OBO document into an `Ontology` using the given options,
+    /// together with the errors that did not prevent the translation.
+    ///
+    /// These are the [`Error::InvalidLiteral`] errors of the literals kept
+    /// as-is in the produced ontology when [`Config::strict_literals`] is
+    /// not set, and the [`Error::InvalidFrame`] errors of the synonyms
+    /// translated as they are written with [`SynonymTypePolicy::Ignore`].
+    ///
+    /// [`Error::InvalidLiteral`]: ./enum.Error.html#variant.InvalidLiteral
+    /// [`Error::InvalidFrame`]: ./enum.Error.html#variant.InvalidFrame
+    /// [`Config::strict_literals`]: ./struct.Config.html#structfield.strict_literals
+    /// [`SynonymTypePolicy::Ignore`]: ./enum.SynonymTypePolicy.html#variant.Ignore
+    fn into_owl_with_warnings<O>(self, config: Config) -> Result<(O, Vec<Error>), Error>
+    where
+        O: Default + MutableOntology<A>;
+
+    /// Convert the OBO document into one OWL `Ontology` per OBO namespace.
+    ///
+    /// Term frames are partitioned using their `namespace` clause (or the
+    /// `default-namespace` of the header), and each partition is translated
+    /// into a separate ontology. Header annotations and typedefs are shared
+    /// by all the produced ontologies, but the ontology IRI of each module
+    /// is derived from the main one: for instance, the `biological_process`
+    /// module of `http://purl.obolibrary.org/obo/go.owl` will be given the
+    /// `http://purl.obolibrary.org/obo/go/biological_process.owl` IRI.
+    fn into_owl_by_namespace<O>(self) -> Result<BTreeMap<obo::NamespaceIdent, O>, Error>
+    where
+        O: Default + MutableOntology<A>;
+
+    /// Convert the OBO document into one OWL `Ontology` per OBO namespace
+    /// using the given options.
+    fn into_owl_by_namespace_with_config<O>(
+        self,
+        config: Config,
+    ) -> Result<BTreeMap<obo::NamespaceIdent, O>, Error>
+    where
+        O: Default + MutableOntology<A>;
+
+    /// Convert the terms of the given subsets into an `Ontology` in OWL language.
+    ///
+    /// Only the terms declared in at least one of the `subsets` are kept,
+    /// together with the typedefs they reference and the `is_a` and
+    /// `relationship` axioms between them. If `closure` is `true`, `is_a`
+    /// edges are added to bridge over the removed intermediate terms,
+    /// connecting each term to its closest ancestors within the subsets.
+    fn into_owl_subset<O>(self, subsets: &[obo::SubsetIdent], closure: bool) -> Result<O, Error>
+    where
+        O: Default + MutableOntology<A>;
+
+    /// Convert the terms of the given subsets into an `Ontology` using the
+    /// given options.
+    fn into_owl_subset_with_config<O>(
+        self,
+        subsets: &[obo::SubsetIdent],
+        closure: bool,
+        config: Config,
+    ) -> Result<O, Error>
+    where
+        O: Default + MutableOntology<A>;
 }
 
 // ---------------------------------------------------------------------------
@@ -72,16 +231,34 @@ lazy_static! {
         obo::RelationIdent::from(obo::UnprefixedIdent::new("all_some"));
 }
 
+/// Get the value of a cardinality qualifier, ignoring invalid values.
+///
+/// Invalid values are reported as errors by [`term::validate`] before
+/// the translation, so they can safely be ignored here.
+fn cardinality(qualifiers: &obo::QualifierList, key: &obo::RelationIdent) -> Option<u32> {
+    qualifiers
+        .iter()
+        .find(|q| q.key() == key)
+        .and_then(|q| q.value().parse().ok())
+}
+
 /// An opaque structure to pass context arguments required for OWL conversion.
 #[derive(Debug)]
 pub struct Context<A: ForIRI> {
     /// The `horned_owl::model::Build` to create reference counted IRI.
     pub build: owl::Build<A>,
 
+    /// The options of the translation.
+    pub config: Config,
+
     /// A mapping of the declared OBO ID spaces to their respective URL bases.
     pub idspaces: HashMap<obo::IdentPrefix, obo::Url>,
 
     /// The IRI of the ontology currently being processed.
+    ///
+    /// This is the URL of the `ontology` header clause if it is a URL, or
+    /// the name of the ontology expanded under [`Config::base_iri`] with
+    /// an `.owl` extension otherwise (e.g. `http://purl.obolibrary.org/obo/go.owl`).
     pub ontology_iri: obo::Url,
 
     /// The IRI of the frame currently being processed.
@@ -95,6 +272,9 @@ pub struct Context<A: ForIRI> {
     #[allow(unused)]
     pub shorthands: HashMap<obo::UnprefixedIdent, obo::Ident>,
 
+    /// A mapping of the declared synonym types to their optional scope.
+    pub synonym_types: HashMap<obo::SynonymTypeIdent, Option<obo::SynonymScope>>,
+
     /// A set of IRI which refer to class level relationships.
     ///
     /// This is likely to require processing imports beforehand.
@@ -105,10 +285,39 @@ pub struct Context<A: ForIRI> {
     /// Properties that are marked as metadata tags are used to record object
     /// metadata and are translated to annotation properties.
     pub metadata_tag: HashSet<owl::IRI<A>>,
+
+    /// A set of IRI which refer to typedefs with a datatype range.
+    ///
+    /// These properties are only translated to data properties when
+    /// [`Config::data_properties`] is enabled, and are otherwise translated
+    /// to object properties like other typedefs.
+    pub data_property: HashSet<owl::IRI<A>>,
+
+    /// A flag to indicate the current frame is a data property.
+    pub in_data_property: bool,
+
+    /// A mapping of the identifiers of anonymous frames to their skolem IRI.
+    pub anonymous: HashMap<obo::Ident, owl::IRI<A>>,
+
+    /// A cache of the IRIs of the identifiers translated so far.
+    ///
+    /// Identifiers such as relations or common superclasses are translated
+    /// many times over a document, so their IRI is only built once.
+    pub iris: HashMap<obo::Ident, owl::IRI<A>>,
+
+    /// The axioms parsed from the `owl-axioms` header clauses.
+    pub owl_axioms: Vec<owl::AnnotatedComponent<A>>,
+
+    /// The errors found in the document that did not prevent its translation.
+    pub warnings: Vec<Error>,
 }
 
 impl<A: ForIRI> Context<A> {
     pub fn from_obodoc(doc: &obo::OboDoc) -> Result<Self, Error> {
+        Self::with_config(doc, Config::default())
+    }
+
+    pub fn with_config(doc: &obo::OboDoc, config: Config) -> Result<Self, Error> {
         // Add the ID spaces declared implicitly in the document.
         let mut idspaces = HashMap::new();
         idspaces.insert(
@@ -123,14 +332,35 @@ impl<A: ForIRI> Context<A> {
             obo::IdentPrefix::new("xsd"),
             obo::Url::new(uri::XSD).unwrap(),
         );
+        idspaces.insert(
+            obo::IdentPrefix::new("rdf"),
+            obo::Url::new(uri::RDF).unwrap(),
+        );
+
+        // Add the ID spaces of the metadata vocabularies, so that well-known
+        // properties such as `dcterms:license` or `owl:versionInfo` expand
+        // to their actual IRI without requiring an `idspace` clause.
+        for (prefix, url) in &[
+            ("owl", uri::OWL),
+            ("rdfs", uri::RDFS),
+            ("dc", uri::DC),
+            ("dcterms", uri::DCTERMS),
+            ("foaf", uri::FOAF),
+        ] {
+            idspaces.insert(obo::IdentPrefix::new(*prefix), obo::Url::new(*url).unwrap());
+        }
 
         // Add the prefixes and ID spaces from the OBO header.
         let mut ontology = Err(Error::Cardinality(CardinalityError::missing("ontology")));
+        let mut synonym_types = HashMap::new();
         for clause in doc.header() {
             match clause {
                 obo::HeaderClause::Idspace(prefix, url, _) => {
                     idspaces.insert(prefix.deref().clone(), url.deref().clone());
                 }
+                obo::HeaderClause::SynonymTypedef(ty, _, scope) => {
+                    synonym_types.insert(ty.deref().clone(), scope.as_deref().cloned());
+                }
                 obo::HeaderClause::Ontology(id) if ontology.is_err() => {
                     ontology = Ok(id.to_string());
                 }
@@ -141,6 +371,11 @@ impl<A: ForIRI> Context<A> {
             }
         }
 
+        // Use the default ontology identifier if the clause is missing.
+        if let (Err(_), Some(default)) = (&ontology, &config.default_ontology) {
+            ontology = Ok(default.clone());
+        }
+
         // Add the shorthands from the OBO typdef
         let mut shorthands = HashMap::new();
         for frame in doc.entities().iter().flat_map(obo::EntityFrame::as_typedef) {
@@ -154,18 +389,51 @@ impl<A: ForIRI> Context<A> {
 
         // Create the conversion context.
         let build = horned_owl::model::Build::<A>::new();
-        let ontology_iri = obo::Url::new(format!("{}{}", uri::OBO, ontology?))?;
+        let ontology = ontology?;
+        let ontology_iri = match obo::Url::new(ontology.as_str()) {
+            Ok(url) => url,
+            Err(_) => {
+                let base = config.base_iri.as_deref().unwrap_or(uri::OBO);
+                obo::Url::new(format!("{}{}.owl", base, ontology))?
+            }
+        };
         let current_frame = build.iri(ontology_iri.as_str().to_string());
         let mut ctx = Context {
             build,
+            config,
             idspaces,
             ontology_iri,
             current_frame,
             shorthands,
+            synonym_types,
             metadata_tag: Default::default(),
             class_level: Default::default(),
+            data_property: Default::default(),
             in_annotation: false,
+            in_data_property: false,
+            anonymous: Default::default(),
+            iris: Default::default(),
+            owl_axioms: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        // Skolemise the identifiers of anonymous frames, so that they are
+        // never expanded to the IRI of a named entity.
+        let skolem_prefix = match &ctx.config.skolem_prefix {
+            Some(prefix) => prefix.clone(),
+            None => format!("{}/.well-known/genid/", ctx.ontology_base()),
         };
+        for entity in doc.entities() {
+            if is_anonymous(entity) {
+                let local = match entity.as_id() {
+                    obo::Ident::Prefixed(id) => format!("{}_{}", id.prefix(), id.local()),
+                    obo::Ident::Unprefixed(id) => id.as_str().to_string(),
+                    obo::Ident::Url(url) => escape_skolem(url.as_str()),
+                };
+                let iri = ctx.build.iri(format!("{}{}", skolem_prefix, local));
+                ctx.anonymous.insert(entity.as_id().clone(), iri);
+            }
+        }
 
         // Retrieve class-level relationships and annotation properties
         //
@@ -174,14 +442,12 @@ impl<A: ForIRI> Context<A> {
         //     already requires a context (in case the typedef has a prefixed
         //     identifier).
         for frame in doc.entities().iter().flat_map(obo::EntityFrame::as_typedef) {
-            let is_metadata_tag = frame.iter().any(|line| match line.as_inner() {
-                obo::TypedefClause::IsMetadataTag(true) => true,
-                _ => false,
-            });
-            let is_class_level = frame.iter().any(|line| match line.as_inner() {
-                obo::TypedefClause::IsClassLevel(true) => true,
-                _ => false,
-            });
+            let is_metadata_tag = frame
+                .iter()
+                .any(|line| matches!(line.as_inner(), obo::TypedefClause::IsMetadataTag(true)));
+            let is_class_level = frame
+                .iter()
+                .any(|line| matches!(line.as_inner(), obo::TypedefClause::IsClassLevel(true)));
             if is_metadata_tag || is_class_level {
                 let iri = frame.id().as_ref().clone().into_owl(&mut ctx);
                 if is_class_level {
@@ -190,12 +456,44 @@ impl<A: ForIRI> Context<A> {
                 if is_metadata_tag {
                     ctx.metadata_tag.insert(iri.clone());
                 }
+            } else if ctx.config.data_properties {
+                let has_datatype_range = frame.iter().any(|line| match line.as_inner() {
+                    obo::TypedefClause::Range(cid) => {
+                        let range: owl::IRI<A> = cid.as_ref().into_owl(&mut ctx);
+                        is_datatype(&range)
+                    }
+                    _ => false,
+                });
+                if has_datatype_range {
+                    let iri = frame.id().as_ref().clone().into_owl(&mut ctx);
+                    ctx.data_property.insert(iri);
+                }
             }
         }
 
         Ok(ctx)
     }
 
+    /// Get the IRI of the ontology without its `.owl` extension.
+    ///
+    /// Unprefixed identifiers are expanded under this IRI, followed by a
+    /// `#` separator (e.g. `http://purl.obolibrary.org/obo/go#part_of`).
+    pub fn ontology_base(&self) -> &str {
+        let iri = self.ontology_iri.as_str();
+        iri.strip_suffix(".owl").unwrap_or(iri)
+    }
+
+    /// Get the version IRI of the ontology for the given data version.
+    ///
+    /// Following the OBO Foundry conventions, the version IRI of
+    /// `http://purl.obolibrary.org/obo/go.owl` for the `2024-01-01` data
+    /// version is `http://purl.obolibrary.org/obo/go/2024-01-01/go.owl`.
+    pub fn version_iri(&self, version: &str) -> owl::IRI<A> {
+        let base = self.ontology_base();
+        let name = base.rsplit('/').next().unwrap_or(base);
+        self.build.iri(format!("{}/{}/{}.owl", base, version, name))
+    }
+
     pub fn find_shorthand(frame: &obo::TypedefFrame) -> Option<&obo::Ident> {
         if let obo::Ident::Unprefixed(_) = frame.id().as_inner().as_ref() {
             // FIXME: right now this takes the first xref of a typedef,
@@ -222,6 +520,28 @@ impl<A: ForIRI> Context<A> {
         self.metadata_tag.contains(rid)
     }
 
+    pub fn is_data_property(&mut self, rid: &owl::IRI<A>) -> bool {
+        self.data_property.contains(rid)
+    }
+
+    pub fn is_anonymous(&self, id: &obo::Ident) -> bool {
+        self.anonymous.contains_key(id)
+    }
+
+    /// Get the annotations for the comment of a line, if they must be kept.
+    pub fn line_comment<T>(&self, line: &obo::Line<T>) -> BTreeSet<owl::Annotation<A>> {
+        let mut annotations = BTreeSet::new();
+        if let Some(comment) = line.comment().filter(|_| self.config.line_comments) {
+            annotations.insert(owl::Annotation {
+                ap: self.build.annotation_property(property::rdfs::COMMENT),
+                av: owl::AnnotationValue::Literal(owl::Literal::Simple {
+                    literal: comment.as_str().to_string(),
+                }),
+            });
+        }
+        annotations
+    }
+
     pub fn rel_class_expression(
         &mut self,
         qualifiers: &obo::QualifierList,
@@ -231,8 +551,7 @@ impl<A: ForIRI> Context<A> {
         let r_iri: owl::IRI<A> = relation.into_owl(self);
         let c_iri: owl::IRI<A> = cls.into_owl(self);
 
-        if let Some(q) = qualifiers.iter().find(|q| q.key() == &*CARDINALITY) {
-            let n: u32 = q.value().parse().expect("invalid value for `cardinality`");
+        if let Some(n) = cardinality(qualifiers, &CARDINALITY) {
             if n == 0 {
                 return owl::ClassExpression::ObjectAllValuesFrom {
                     ope: owl::ObjectPropertyExpression::ObjectProperty(owl::ObjectProperty(r_iri)),
@@ -249,31 +568,17 @@ impl<A: ForIRI> Context<A> {
             }
         }
 
-        if let Some(q) = qualifiers.iter().find(|q| q.key() == &*MAX_CARDINALITY) {
-            let na: i32 = q
-                .value()
-                .parse()
-                .expect("invalid value for `maxCardinality`");
-            if na == 0 {
-                return owl::ClassExpression::ObjectAllValuesFrom {
-                    ope: owl::ObjectPropertyExpression::ObjectProperty(owl::ObjectProperty(r_iri)),
-                    bce: Box::new(owl::ClassExpression::ObjectComplementOf(Box::new(
-                        owl::Class(c_iri).into(),
-                    ))),
-                };
-            }
+        if let Some(0) = cardinality(qualifiers, &MAX_CARDINALITY) {
+            return owl::ClassExpression::ObjectAllValuesFrom {
+                ope: owl::ObjectPropertyExpression::ObjectProperty(owl::ObjectProperty(r_iri)),
+                bce: Box::new(owl::ClassExpression::ObjectComplementOf(Box::new(
+                    owl::Class(c_iri).into(),
+                ))),
+            };
         }
 
-        if let Some(qa) = qualifiers.iter().find(|q| q.key() == &*MIN_CARDINALITY) {
-            let na = qa
-                .value()
-                .parse()
-                .expect("invalid value for `min_cardinality`");
-            if let Some(qb) = qualifiers.iter().find(|q| q.key() == &*MAX_CARDINALITY) {
-                let nb = qb
-                    .value()
-                    .parse()
-                    .expect("invalid value for `max_cardinality`");
+        if let Some(na) = cardinality(qualifiers, &MIN_CARDINALITY) {
+            if let Some(nb) = cardinality(qualifiers, &MAX_CARDINALITY) {
                 return owl::ClassExpression::ObjectIntersectionOf(vec![
                     owl::ClassExpression::ObjectMinCardinality {
                         n: na,
@@ -299,15 +604,11 @@ impl<A: ForIRI> Context<A> {
             }
         }
 
-        if let Some(q) = qualifiers.iter().find(|q| q.key() == &*MAX_CARDINALITY) {
+        if let Some(n) = cardinality(qualifiers, &MAX_CARDINALITY) {
             return owl::ClassExpression::ObjectMaxCardinality {
-                n: q.value()
-                    .parse()
-                    .expect("invalid value for `maxCardinality`"),
+                n,
                 ope: owl::ObjectPropertyExpression::ObjectProperty(owl::ObjectProperty(r_iri)),
-                bce: Box::new(owl::ClassExpression::ObjectComplementOf(Box::new(
-                    owl::Class(c_iri).into(),
-                ))),
+                bce: Box::new(owl::Class(c_iri).into()),
             };
         }
 
@@ -350,6 +651,42 @@ impl<A: ForIRI> Context<A> {
     }
 }
 
+/// Check whether an IRI refers to a datatype rather than to a class.
+fn is_datatype<A: ForIRI>(iri: &owl::IRI<A>) -> bool {
+    iri.starts_with(uri::XSD)
+        || iri.as_ref() == crate::constants::datatype::rdf::LANG_STRING
+        || iri.as_ref() == crate::constants::datatype::rdfs::LITERAL
+}
+
+/// Percent-encode an URL so that it can be used as a skolem IRI segment.
+fn escape_skolem(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for byte in url.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                escaped.push(byte as char)
+            }
+            _ => escaped.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    escaped
+}
+
+/// Check whether an entity frame is declared with `is_anonymous: true`.
+fn is_anonymous(entity: &obo::EntityFrame) -> bool {
+    match entity {
+        obo::EntityFrame::Term(frame) => frame
+            .iter()
+            .any(|line| matches!(line.as_inner(), obo::TermClause::IsAnonymous(true))),
+        obo::EntityFrame::Typedef(frame) => frame
+            .iter()
+            .any(|line| matches!(line.as_inner(), obo::TypedefClause::IsAnonymous(true))),
+        obo::EntityFrame::Instance(frame) => frame
+            .iter()
+            .any(|line| matches!(line.as_inner(), obo::InstanceClause::IsAnonymous(true))),
+    }
+}
+
 impl<A: ForIRI> TryFrom<&obo::OboDoc> for Context<A> {
     type Error = Error;
     fn try_from(doc: &obo::OboDoc) -> Result<Self, Error> {
@@ -390,4 +727,105 @@ mod tests {
             ))
         ));
     }
+
+    #[test]
+    fn known_clause_tags() {
+        let clause = obo::TypedefClause::IsMetadataTag(true);
+        assert_eq!(clause_tag(&clause), Some("is_metadata_tag"));
+        let clause = obo::HeaderClause::Remark(Box::new(obo::UnquotedString::new("x")));
+        assert_eq!(clause_tag(&clause), Some("remark"));
+        let clause = obo::HeaderClause::Unreserved(
+            Box::new(obo::UnquotedString::new("tag")),
+            Box::new(obo::UnquotedString::new("value")),
+        );
+        assert_eq!(clause_tag(&clause), None);
+    }
+
+    #[test]
+    fn skolemise_url() {
+        let doc = fastobo::from_str(
+            "ontology: test\n\n[Term]\nid: http://example.com/tst#01\nis_anonymous: true\n",
+        )
+        .unwrap();
+        let ctx = Context::<String>::from_obodoc(&doc).unwrap();
+        let id = doc.entities()[0].as_id();
+        assert_eq!(
+            ctx.anonymous.get(id).map(|iri| iri.as_ref()),
+            Some("http://purl.obolibrary.org/obo/test/.well-known/genid/http%3A%2F%2Fexample.com%2Ftst%2301")
+        );
+    }
+
+    #[test]
+    fn cached_iri() {
+        let doc =
+            fastobo::from_str("ontology: test\nidspace: TST http://example.com/tst/\n").unwrap();
+        let mut ctx = Context::<String>::from_obodoc(&doc).unwrap();
+        let id = obo::Ident::from(obo::PrefixedIdent::new("TST", "001"));
+
+        let iri: owl::IRI<String> = id.into_owl(&mut ctx);
+        assert_eq!(iri.as_ref(), "http://example.com/tst/001");
+        assert_eq!(ctx.iris.get(&id), Some(&iri));
+        assert_eq!(id.into_owl(&mut ctx), iri);
+    }
+
+    #[test]
+    fn url_ontology_clause() {
+        let doc = fastobo::from_str("ontology: https://w3id.org/example/test.owl\n").unwrap();
+        let mut ctx = Context::<String>::from_obodoc(&doc).unwrap();
+        assert_eq!(
+            ctx.ontology_iri.as_str(),
+            "https://w3id.org/example/test.owl"
+        );
+
+        let id = obo::Ident::from(obo::UnprefixedIdent::new("part_of"));
+        let iri: owl::IRI<String> = id.into_owl(&mut ctx);
+        assert_eq!(iri.as_ref(), "https://w3id.org/example/test#part_of");
+        assert_eq!(
+            ctx.version_iri("1.0").as_ref(),
+            "https://w3id.org/example/test/1.0/test.owl"
+        );
+    }
+
+    #[test]
+    fn base_iri() {
+        let doc = fastobo::from_str("ontology: test\n").unwrap();
+        let config = Config {
+            base_iri: Some(String::from("https://w3id.org/example/")),
+            ..Default::default()
+        };
+        let mut ctx = Context::<String>::with_config(&doc, config).unwrap();
+        assert_eq!(
+            ctx.ontology_iri.as_str(),
+            "https://w3id.org/example/test.owl"
+        );
+
+        let id = obo::Ident::from(obo::UnprefixedIdent::new("part_of"));
+        let iri: owl::IRI<String> = id.into_owl(&mut ctx);
+        assert_eq!(iri.as_ref(), "https://w3id.org/example/test#part_of");
+    }
+
+    #[test]
+    fn default_ontology() {
+        let doc = obo::OboDoc::new();
+        let config = Config {
+            default_ontology: Some(String::from("test")),
+            ..Default::default()
+        };
+        let ctx = Context::<String>::with_config(&doc, config).unwrap();
+        assert_eq!(
+            ctx.ontology_iri.as_str(),
+            "http://purl.obolibrary.org/obo/test.owl"
+        );
+
+        let doc = fastobo::from_str("ontology: other\n").unwrap();
+        let config = Config {
+            default_ontology: Some(String::from("test")),
+            ..Default::default()
+        };
+        let ctx = Context::<String>::with_config(&doc, config).unwrap();
+        assert_eq!(
+            ctx.ontology_iri.as_str(),
+            "http://purl.obolibrary.org/obo/other.owl"
+        );
+    }
 }
@@ -0,0 +1,516 @@
+use std::collections::HashSet;
+
+use horned_owl::model as owl;
+use horned_owl::model::ForIRI;
+
+use crate::config::Profile;
+use crate::constants::class;
+
+/// The position of a class expression in an axiom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Position {
+    Sub,
+    Super,
+    Equivalent,
+}
+
+/// The result of a check, with the name of the offending construct on error.
+type Check = Result<(), &'static str>;
+
+/// Get the name of the construct of a class expression.
+fn construct<A: ForIRI>(ce: &owl::ClassExpression<A>) -> &'static str {
+    use owl::ClassExpression::*;
+    match ce {
+        Class(_) => "Class",
+        ObjectIntersectionOf(_) => "ObjectIntersectionOf",
+        ObjectUnionOf(_) => "ObjectUnionOf",
+        ObjectComplementOf(_) => "ObjectComplementOf",
+        ObjectOneOf(_) => "ObjectOneOf",
+        ObjectSomeValuesFrom { .. } => "ObjectSomeValuesFrom",
+        ObjectAllValuesFrom { .. } => "ObjectAllValuesFrom",
+        ObjectHasValue { .. } => "ObjectHasValue",
+        ObjectHasSelf(_) => "ObjectHasSelf",
+        ObjectMinCardinality { .. } => "ObjectMinCardinality",
+        ObjectMaxCardinality { .. } => "ObjectMaxCardinality",
+        ObjectExactCardinality { .. } => "ObjectExactCardinality",
+        DataSomeValuesFrom { .. } => "DataSomeValuesFrom",
+        DataAllValuesFrom { .. } => "DataAllValuesFrom",
+        DataHasValue { .. } => "DataHasValue",
+        DataMinCardinality { .. } => "DataMinCardinality",
+        DataMaxCardinality { .. } => "DataMaxCardinality",
+        DataExactCardinality { .. } => "DataExactCardinality",
+    }
+}
+
+/// Check whether a class expression is `owl:Thing`.
+fn is_thing<A: ForIRI>(ce: &owl::ClassExpression<A>) -> bool {
+    matches!(ce, owl::ClassExpression::Class(c) if c.0.as_ref() == class::owl::THING)
+}
+
+/// A checker for the OWL 2 profile of the axioms produced by the translation.
+///
+/// The checks follow the grammars of the
+/// [OWL 2 profiles](https://www.w3.org/TR/owl2-profiles/) for the constructs
+/// that can be produced from an OBO document. For OWL 2 DL, only the global
+/// restriction on non-simple properties is checked, since IRIs used in
+/// conflicting roles are handled by
+/// [`Config::role_conflicts`](../../struct.Config.html#structfield.role_conflicts).
+#[derive(Debug)]
+pub struct Checker<A: ForIRI> {
+    profile: Profile,
+    non_simple: HashSet<owl::IRI<A>>,
+}
+
+impl<A: ForIRI> Checker<A> {
+    /// Create a new checker for the given profile and ontology axioms.
+    pub fn new<'a, I>(profile: Profile, axioms: I) -> Self
+    where
+        A: 'a,
+        I: IntoIterator<Item = &'a owl::AnnotatedComponent<A>>,
+    {
+        // Collect the non-simple properties, which are needed to check
+        // the global restrictions of OWL 2 DL, and the property hierarchy
+        // they propagate through (from a property to its super-properties,
+        // and between equivalent or inverse properties).
+        let mut non_simple = HashSet::new();
+        let mut edges = Vec::new();
+        for axiom in axioms {
+            match &axiom.component {
+                owl::Component::TransitiveObjectProperty(owl::TransitiveObjectProperty(ope)) => {
+                    non_simple.insert(Self::property_iri(ope).clone());
+                }
+                owl::Component::SubObjectPropertyOf(ax) => match &ax.sub {
+                    owl::SubObjectPropertyExpression::ObjectPropertyChain(_) => {
+                        non_simple.insert(Self::property_iri(&ax.sup).clone());
+                    }
+                    owl::SubObjectPropertyExpression::ObjectPropertyExpression(sub) => {
+                        edges.push((Self::property_iri(sub), Self::property_iri(&ax.sup)));
+                    }
+                },
+                owl::Component::InverseObjectProperties(owl::InverseObjectProperties(p1, p2)) => {
+                    edges.push((&p1.0, &p2.0));
+                    edges.push((&p2.0, &p1.0));
+                }
+                owl::Component::EquivalentObjectProperties(owl::EquivalentObjectProperties(
+                    opes,
+                )) => {
+                    for (p1, p2) in opes.iter().zip(opes.iter().skip(1)) {
+                        edges.push((Self::property_iri(p1), Self::property_iri(p2)));
+                        edges.push((Self::property_iri(p2), Self::property_iri(p1)));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        // Propagate the non-simple properties until a fixpoint is reached.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (from, to) in edges.iter() {
+                if non_simple.contains(*from) && !non_simple.contains(*to) {
+                    non_simple.insert((*to).clone());
+                    changed = true;
+                }
+            }
+        }
+
+        Self {
+            profile,
+            non_simple,
+        }
+    }
+
+    fn property_iri(ope: &owl::ObjectPropertyExpression<A>) -> &owl::IRI<A> {
+        match ope {
+            owl::ObjectPropertyExpression::ObjectProperty(op) => &op.0,
+            owl::ObjectPropertyExpression::InverseObjectProperty(op) => &op.0,
+        }
+    }
+
+    /// Check that a property expression can be used in a simple position.
+    fn check_simple(&self, ope: &owl::ObjectPropertyExpression<A>) -> Check {
+        if self.non_simple.contains(Self::property_iri(ope)) {
+            Err("non-simple property")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_property(&self, ope: &owl::ObjectPropertyExpression<A>) -> Check {
+        match (self.profile, ope) {
+            (Profile::EL, owl::ObjectPropertyExpression::InverseObjectProperty(_)) => {
+                Err("ObjectInverseOf")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_all(&self, ces: &[owl::ClassExpression<A>], pos: Position) -> Check {
+        ces.iter().try_for_each(|ce| self.check_expression(ce, pos))
+    }
+
+    fn check_expression(&self, ce: &owl::ClassExpression<A>, pos: Position) -> Check {
+        use self::Position::*;
+        use owl::ClassExpression::*;
+        match self.profile {
+            Profile::DL => match ce {
+                ObjectIntersectionOf(v) | ObjectUnionOf(v) => self.check_all(v, pos),
+                ObjectComplementOf(c) => self.check_expression(c, pos),
+                ObjectSomeValuesFrom { bce, .. } | ObjectAllValuesFrom { bce, .. } => {
+                    self.check_expression(bce, pos)
+                }
+                ObjectMinCardinality { ope, bce, .. }
+                | ObjectMaxCardinality { ope, bce, .. }
+                | ObjectExactCardinality { ope, bce, .. } => {
+                    self.check_simple(ope)?;
+                    self.check_expression(bce, pos)
+                }
+                ObjectHasSelf(ope) => self.check_simple(ope),
+                _ => Ok(()),
+            },
+            Profile::EL => match ce {
+                Class(_) | DataSomeValuesFrom { .. } | DataHasValue { .. } => Ok(()),
+                ObjectIntersectionOf(v) => self.check_all(v, pos),
+                ObjectSomeValuesFrom { ope, bce } => {
+                    self.check_property(ope)?;
+                    self.check_expression(bce, pos)
+                }
+                ObjectHasValue { ope, .. } | ObjectHasSelf(ope) => self.check_property(ope),
+                ObjectOneOf(v) if v.len() == 1 => Ok(()),
+                other => Err(construct(other)),
+            },
+            Profile::QL => match (pos, ce) {
+                (_, Class(_)) | (_, DataSomeValuesFrom { .. }) => Ok(()),
+                (Sub, ObjectSomeValuesFrom { bce, .. })
+                | (Equivalent, ObjectSomeValuesFrom { bce, .. })
+                    if is_thing(bce) =>
+                {
+                    Ok(())
+                }
+                (Super, ObjectSomeValuesFrom { bce, .. }) if matches!(**bce, Class(_)) => Ok(()),
+                (Super, ObjectIntersectionOf(v)) => self.check_all(v, Super),
+                (Super, ObjectComplementOf(c)) => self.check_expression(c, Sub),
+                (_, other) => Err(construct(other)),
+            },
+            Profile::RL => match (pos, ce) {
+                (_, Class(_)) if !is_thing(ce) => Ok(()),
+                (_, ObjectHasValue { .. }) | (_, DataHasValue { .. }) => Ok(()),
+                (_, ObjectIntersectionOf(v)) => self.check_all(v, pos),
+                (Sub, ObjectUnionOf(v)) => self.check_all(v, Sub),
+                (Sub, ObjectOneOf(_)) | (Sub, DataSomeValuesFrom { .. }) => Ok(()),
+                (Sub, ObjectSomeValuesFrom { bce, .. }) if is_thing(bce) => Ok(()),
+                (Sub, ObjectSomeValuesFrom { bce, .. }) => self.check_expression(bce, Sub),
+                (Super, ObjectComplementOf(c)) => self.check_expression(c, Sub),
+                (Super, ObjectAllValuesFrom { bce, .. }) => self.check_expression(bce, Super),
+                (Super, ObjectMaxCardinality { n, bce, .. }) if *n <= 1 && is_thing(bce) => Ok(()),
+                (Super, ObjectMaxCardinality { n, bce, .. }) if *n <= 1 => {
+                    self.check_expression(bce, Sub)
+                }
+                (Super, DataAllValuesFrom { .. }) => Ok(()),
+                (Super, DataMaxCardinality { n, .. }) if *n <= 1 => Ok(()),
+                (_, other) => Err(construct(other)),
+            },
+        }
+    }
+
+    fn check_component(&self, component: &owl::Component<A>) -> Check {
+        use self::Position::*;
+        use owl::Component::*;
+        use Profile::*;
+        let profile = self.profile;
+        match component {
+            SubClassOf(ax) => {
+                self.check_expression(&ax.sub, Sub)?;
+                self.check_expression(&ax.sup, Super)
+            }
+            EquivalentClasses(ax) => self.check_all(&ax.0, Equivalent),
+            DisjointClasses(ax) => self.check_all(&ax.0, Sub),
+            DisjointUnion(_) if profile != DL => Err("DisjointUnion"),
+            ObjectPropertyDomain(ax) => {
+                self.check_property(&ax.ope)?;
+                self.check_expression(&ax.ce, Super)
+            }
+            ObjectPropertyRange(ax) => {
+                self.check_property(&ax.ope)?;
+                self.check_expression(&ax.ce, Super)
+            }
+            DataPropertyDomain(ax) => self.check_expression(&ax.ce, Super),
+            ClassAssertion(ax)
+                if profile == QL && !matches!(ax.ce, owl::ClassExpression::Class(_)) =>
+            {
+                Err(construct(&ax.ce))
+            }
+            ClassAssertion(ax) => self.check_expression(&ax.ce, Super),
+            SubObjectPropertyOf(ax) => match &ax.sub {
+                owl::SubObjectPropertyExpression::ObjectPropertyChain(_) if profile == QL => {
+                    Err("ObjectPropertyChain")
+                }
+                owl::SubObjectPropertyExpression::ObjectPropertyChain(chain) => {
+                    chain.iter().try_for_each(|ope| self.check_property(ope))?;
+                    self.check_property(&ax.sup)
+                }
+                owl::SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                    self.check_property(ope)?;
+                    self.check_property(&ax.sup)
+                }
+            },
+            FunctionalObjectProperty(_) if profile == EL || profile == QL => {
+                Err("FunctionalObjectProperty")
+            }
+            InverseFunctionalObjectProperty(_) if profile == EL || profile == QL => {
+                Err("InverseFunctionalObjectProperty")
+            }
+            IrreflexiveObjectProperty(_) if profile == EL => Err("IrreflexiveObjectProperty"),
+            SymmetricObjectProperty(_) if profile == EL => Err("SymmetricObjectProperty"),
+            AsymmetricObjectProperty(_) if profile == EL => Err("AsymmetricObjectProperty"),
+            DisjointObjectProperties(_) if profile == EL => Err("DisjointObjectProperties"),
+            InverseObjectProperties(_) if profile == EL => Err("InverseObjectProperties"),
+            TransitiveObjectProperty(_) if profile == QL => Err("TransitiveObjectProperty"),
+            ReflexiveObjectProperty(_) if profile == RL => Err("ReflexiveObjectProperty"),
+            FunctionalDataProperty(_) if profile == QL => Err("FunctionalDataProperty"),
+            HasKey(_) if profile == QL => Err("HasKey"),
+            SameIndividual(_) if profile == QL => Err("SameIndividual"),
+            FunctionalObjectProperty(owl::FunctionalObjectProperty(ope))
+            | InverseFunctionalObjectProperty(owl::InverseFunctionalObjectProperty(ope))
+            | IrreflexiveObjectProperty(owl::IrreflexiveObjectProperty(ope))
+            | AsymmetricObjectProperty(owl::AsymmetricObjectProperty(ope))
+                if profile == DL =>
+            {
+                self.check_simple(ope)
+            }
+            DisjointObjectProperties(owl::DisjointObjectProperties(opes)) if profile == DL => {
+                opes.iter().try_for_each(|ope| self.check_simple(ope))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check whether an axiom is in the profile.
+    ///
+    /// Returns a description of the violation if the axiom is out of profile.
+    pub fn check(&self, axiom: &owl::AnnotatedComponent<A>) -> Result<(), String> {
+        self.check_component(&axiom.component).map_err(|construct| {
+            format!("{} is not allowed in OWL 2 {:?}", construct, self.profile)
+        })
+    }
+
+    /// Weaken a superclass expression so that it may fit in the profile.
+    ///
+    /// Qualified cardinality restrictions with a non-zero lower bound are
+    /// replaced with an existential restriction on the same class, which
+    /// is entailed by the original expression.
+    fn approximate_expression(&self, ce: owl::ClassExpression<A>) -> owl::ClassExpression<A> {
+        use owl::ClassExpression::*;
+        match ce {
+            ObjectIntersectionOf(v) => {
+                // Conjuncts still out of profile can be removed, since the
+                // remaining intersection is entailed by the original one.
+                let mut conjuncts = v
+                    .into_iter()
+                    .map(|ce| self.approximate_expression(ce))
+                    .collect::<Vec<_>>();
+                if conjuncts
+                    .iter()
+                    .any(|ce| self.check_expression(ce, Position::Super).is_ok())
+                {
+                    conjuncts.retain(|ce| self.check_expression(ce, Position::Super).is_ok());
+                }
+                match conjuncts.len() {
+                    1 => conjuncts.remove(0),
+                    _ => ObjectIntersectionOf(conjuncts),
+                }
+            }
+            ObjectExactCardinality { n, ope, bce } | ObjectMinCardinality { n, ope, bce }
+                if n > 0 =>
+            {
+                ObjectSomeValuesFrom { ope, bce }
+            }
+            ObjectSomeValuesFrom { ope, bce } => ObjectSomeValuesFrom {
+                ope,
+                bce: Box::new(self.approximate_expression(*bce)),
+            },
+            other => other,
+        }
+    }
+
+    /// Approximate an axiom so that it fits in the profile.
+    ///
+    /// Axioms already in the profile are returned unchanged. Equivalence
+    /// axioms are weakened to subclass axioms for each of their named
+    /// classes, and superclass expressions are approximated when possible.
+    /// The resulting axioms which are still out of profile are removed.
+    pub fn approximate(
+        &self,
+        axiom: owl::AnnotatedComponent<A>,
+    ) -> Vec<owl::AnnotatedComponent<A>> {
+        if self.check(&axiom).is_ok() {
+            return vec![axiom];
+        }
+
+        let ann = axiom.ann;
+        let weakened = match axiom.component {
+            owl::Component::SubClassOf(ax) => vec![owl::SubClassOf {
+                sub: ax.sub,
+                sup: self.approximate_expression(ax.sup),
+            }],
+            owl::Component::EquivalentClasses(ax) => {
+                let mut weakened = Vec::new();
+                for sub in
+                    ax.0.iter()
+                        .filter(|ce| matches!(ce, owl::ClassExpression::Class(_)))
+                {
+                    for sup in ax.0.iter().filter(|ce| *ce != sub) {
+                        weakened.push(owl::SubClassOf {
+                            sub: sub.clone(),
+                            sup: self.approximate_expression(sup.clone()),
+                        });
+                    }
+                }
+                weakened
+            }
+            _ => Vec::new(),
+        };
+
+        weakened
+            .into_iter()
+            .map(|ax| owl::AnnotatedComponent::new(owl::Component::SubClassOf(ax), ann.clone()))
+            .filter(|ac| self.check(ac).is_ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fastobo::ast as obo;
+    use horned_owl::model::MutableOntology;
+    use horned_owl::ontology::set::SetOntology;
+
+    use super::*;
+    use crate::Config;
+    use crate::Error;
+    use crate::IntoOwl;
+    use crate::ProfilePolicy;
+    use crate::ProfileViolation;
+
+    const DOC: &str = "ontology: test\ndefault-namespace: test\n\n[Term]\nid: TST:001\nintersection_of: TST:002\nintersection_of: TST:003 TST:004 {cardinality=\"2\"}\n\n[Term]\nid: TST:005\nunion_of: TST:002\nunion_of: TST:004\n\n[Typedef]\nid: TST:003\nis_transitive: true\n";
+
+    fn convert(profile: Profile, policy: ProfilePolicy) -> Result<SetOntology<String>, Error> {
+        convert_doc(DOC, profile, policy)
+    }
+
+    fn convert_doc(
+        doc: &str,
+        profile: Profile,
+        policy: ProfilePolicy,
+    ) -> Result<SetOntology<String>, Error> {
+        let config = Config {
+            profile: Some(profile),
+            profile_policy: policy,
+            ..Config::default()
+        };
+        fastobo::from_str(doc).unwrap().into_owl_with_config(config)
+    }
+
+    fn subclass_axioms(ont: SetOntology<String>) -> Vec<owl::SubClassOf<String>> {
+        ont.into_iter()
+            .filter_map(|ac| match ac.component {
+                owl::Component::SubClassOf(ax) => Some(ax),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn el_reports_frame_and_clause() {
+        match convert(Profile::EL, ProfilePolicy::Error) {
+            Err(Error::ProfileViolations(violations)) => {
+                assert_eq!(
+                    violations,
+                    vec![
+                        ProfileViolation {
+                            frame: Some(obo::PrefixedIdent::new("TST", "001").into()),
+                            clause: Some(String::from("intersection_of")),
+                            reason: String::from(
+                                "ObjectExactCardinality is not allowed in OWL 2 EL"
+                            ),
+                        },
+                        ProfileViolation {
+                            frame: Some(obo::PrefixedIdent::new("TST", "005").into()),
+                            clause: Some(String::from("union_of")),
+                            reason: String::from("ObjectUnionOf is not allowed in OWL 2 EL"),
+                        },
+                    ]
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn el_approximates_cardinality() {
+        let ont = convert(Profile::EL, ProfilePolicy::Approximate).unwrap();
+        let build = horned_owl::model::Build::new_string();
+        let expected = owl::SubClassOf {
+            sub: owl::ClassExpression::Class(build.class("http://purl.obolibrary.org/obo/TST_001")),
+            sup: owl::ClassExpression::ObjectIntersectionOf(vec![
+                build.class("http://purl.obolibrary.org/obo/TST_002").into(),
+                owl::ClassExpression::ObjectSomeValuesFrom {
+                    ope: build
+                        .object_property("http://purl.obolibrary.org/obo/TST_003")
+                        .into(),
+                    bce: Box::new(build.class("http://purl.obolibrary.org/obo/TST_004").into()),
+                },
+            ]),
+        };
+        assert_eq!(subclass_axioms(ont), vec![expected]);
+    }
+
+    #[test]
+    fn ql_drops_transitive() {
+        let mut ont = convert(Profile::QL, ProfilePolicy::Drop).unwrap();
+        let build = horned_owl::model::Build::new_string();
+        let transitive = owl::TransitiveObjectProperty(
+            build
+                .object_property("http://purl.obolibrary.org/obo/TST_003")
+                .into(),
+        );
+        assert!(!ont.remove(&owl::AnnotatedComponent::from(transitive)));
+    }
+
+    #[test]
+    fn dl_rejects_non_simple_cardinality() {
+        match convert(Profile::DL, ProfilePolicy::Error) {
+            Err(Error::ProfileViolations(violations)) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(
+                    violations[0].reason,
+                    "non-simple property is not allowed in OWL 2 DL"
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn dl_propagates_non_simple_properties() {
+        let doc = "ontology: test\ndefault-namespace: test\n\n[Term]\nid: TST:001\nintersection_of: TST:002\nintersection_of: TST:004 TST:002 {cardinality=\"2\"}\n\n[Term]\nid: TST:005\nintersection_of: TST:002\nintersection_of: TST:006 TST:002 {maxCardinality=\"1\"}\n\n[Typedef]\nid: TST:003\nis_a: TST:004\nis_transitive: true\n\n[Typedef]\nid: TST:004\n\n[Typedef]\nid: TST:006\ninverse_of: TST:004\n";
+        match convert_doc(doc, Profile::DL, ProfilePolicy::Error) {
+            Err(Error::ProfileViolations(violations)) => {
+                let frames = violations
+                    .iter()
+                    .map(|v| v.frame.clone())
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    frames,
+                    vec![
+                        Some(obo::PrefixedIdent::new("TST", "001").into()),
+                        Some(obo::PrefixedIdent::new("TST", "005").into()),
+                    ]
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
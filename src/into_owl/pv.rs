@@ -1,10 +1,18 @@
 use fastobo::ast as obo;
+use fastobo::semantics::Identified;
 use horned_owl::model as owl;
 use horned_owl::model::ForIRI;
 
 use super::Context;
 use super::IntoOwlCtx;
+use crate::constants::datatype::rdf;
 use crate::constants::datatype::xsd;
+use crate::error::Error;
+
+lazy_static! {
+    static ref LANG: obo::RelationIdent =
+        obo::RelationIdent::from(obo::UnprefixedIdent::new("lang"));
+}
 
 fn is_xsd_string<A: ForIRI>(ctx: &Context<A>, id: &obo::Ident) -> bool {
     match id {
@@ -20,6 +28,352 @@ fn is_xsd_string<A: ForIRI>(ctx: &Context<A>, id: &obo::Ident) -> bool {
     }
 }
 
+// --- Lexical validation ----------------------------------------------------
+
+/// Split the sign of a numeric literal from its digits.
+fn split_sign(s: &str) -> (bool, &str) {
+    match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    }
+}
+
+fn is_digits(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn canonical_integer(s: &str) -> Option<String> {
+    let (negative, digits) = split_sign(s);
+    if digits.is_empty() || !is_digits(digits) {
+        return None;
+    }
+    match digits.trim_start_matches('0') {
+        "" => Some(String::from("0")),
+        digits if negative => Some(format!("-{}", digits)),
+        digits => Some(digits.to_string()),
+    }
+}
+
+fn canonical_decimal(s: &str) -> Option<String> {
+    let (negative, digits) = split_sign(s);
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    if int.len() + frac.len() == 0 || !is_digits(int) || !is_digits(frac) {
+        return None;
+    }
+    let int = match int.trim_start_matches('0') {
+        "" => "0",
+        int => int,
+    };
+    let frac = frac.trim_end_matches('0');
+    let sign = if negative && (int != "0" || !frac.is_empty()) {
+        "-"
+    } else {
+        ""
+    };
+    if frac.is_empty() {
+        Some(format!("{}{}", sign, int))
+    } else {
+        Some(format!("{}{}.{}", sign, int, frac))
+    }
+}
+
+fn canonical_boolean(s: &str) -> Option<String> {
+    match s {
+        "true" | "1" => Some(String::from("true")),
+        "false" | "0" => Some(String::from("false")),
+        _ => None,
+    }
+}
+
+/// Parse a fixed-width number made of `n` digits.
+fn parse_fixed(s: &str, n: usize) -> Option<u32> {
+    if s.len() == n && is_digits(s) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// A date of the proleptic Gregorian calendar, as a year, month and day.
+type Date = (i64, u32, u32);
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Get the day following a date.
+fn next_day((year, month, day): Date) -> Date {
+    if day < days_in_month(year, month) {
+        (year, month, day + 1)
+    } else if month < 12 {
+        (year, month + 1, 1)
+    } else {
+        (year + 1, 1, 1)
+    }
+}
+
+fn format_date((year, month, day): Date) -> String {
+    let sign = if year < 0 { "-" } else { "" };
+    format!("{}{:04}-{:02}-{:02}", sign, year.abs(), month, day)
+}
+
+/// Validate a `YYYY-MM-DD` date, returning the remainder.
+fn parse_date_part(s: &str) -> Option<(Date, &str)> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (year, rest) = s.split_once('-')?;
+    if year.len() < 4 || !is_digits(year) || (year.len() > 4 && year.starts_with('0')) {
+        return None;
+    }
+    let year: i64 = year.parse().ok()?;
+    let year = if negative { -year } else { year };
+    let month = parse_fixed(rest.get(..2)?, 2)?;
+    let day = parse_fixed(rest.get(3..5)?, 2)?;
+    if rest.get(2..3)? != "-" || !(1..=12).contains(&month) {
+        return None;
+    }
+    if day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(((year, month, day), &rest[5..]))
+}
+
+/// Validate and canonicalize an optional timezone.
+fn canonical_timezone(s: &str) -> Option<String> {
+    match s {
+        "" => Some(String::new()),
+        "Z" | "+00:00" | "-00:00" => Some(String::from("Z")),
+        _ => {
+            let sign = s.get(..1)?;
+            let hours = parse_fixed(s.get(1..3)?, 2)?;
+            let minutes = parse_fixed(s.get(4..)?, 2)?;
+            let valid = (sign == "+" || sign == "-")
+                && s.get(3..4)? == ":"
+                && minutes < 60
+                && (hours < 14 || (hours == 14 && minutes == 0));
+            if valid {
+                Some(s.to_string())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn canonical_date(s: &str) -> Option<String> {
+    let (date, rest) = parse_date_part(s)?;
+    Some(format!(
+        "{}{}",
+        format_date(date),
+        canonical_timezone(rest)?
+    ))
+}
+
+fn canonical_datetime(s: &str) -> Option<String> {
+    let (date, rest) = parse_date_part(s)?;
+    let rest = rest.strip_prefix('T')?;
+    let hours = parse_fixed(rest.get(..2)?, 2)?;
+    let minutes = parse_fixed(rest.get(3..5)?, 2)?;
+    let seconds = parse_fixed(rest.get(6..8)?, 2)?;
+    if rest.get(2..3)? != ":" || rest.get(5..6)? != ":" {
+        return None;
+    }
+
+    // Extract fractional seconds, if any.
+    let rest = &rest[8..];
+    let (frac, tz) = match rest.strip_prefix('.') {
+        Some(r) => {
+            let end = r.find(|c: char| !c.is_ascii_digit()).unwrap_or(r.len());
+            if end == 0 {
+                return None;
+            }
+            (r[..end].trim_end_matches('0'), &r[end..])
+        }
+        None => ("", rest),
+    };
+
+    let midnight = hours == 24 && minutes == 0 && seconds == 0 && frac.is_empty();
+    if (hours > 23 && !midnight) || minutes > 59 || seconds > 59 {
+        return None;
+    }
+
+    // `24:00:00` is the midnight ending the day, i.e. `00:00:00` on the next day.
+    let (date, hours) = if midnight {
+        (next_day(date), 0)
+    } else {
+        (date, hours)
+    };
+
+    let mut out = format!(
+        "{}T{:02}:{:02}:{:02}",
+        format_date(date),
+        hours,
+        minutes,
+        seconds
+    );
+    if !frac.is_empty() {
+        out.push('.');
+        out.push_str(frac);
+    }
+    out.push_str(&canonical_timezone(tz)?);
+    Some(out)
+}
+
+fn canonical_any_uri(s: &str) -> Option<String> {
+    if s.chars().any(char::is_whitespace) {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
+
+/// Get the canonical form of a literal with a well-known datatype.
+///
+/// Returns `None` if the literal is not in the lexical space of the given
+/// datatype. Literals with another datatype are returned unchanged.
+pub fn canonicalize(datatype: &str, literal: &str) -> Option<String> {
+    match datatype {
+        xsd::INTEGER => canonical_integer(literal.trim()),
+        xsd::DECIMAL => canonical_decimal(literal.trim()),
+        xsd::BOOLEAN => canonical_boolean(literal.trim()),
+        xsd::DATE => canonical_date(literal.trim()),
+        xsd::DATETIME => canonical_datetime(literal.trim()),
+        xsd::ANY_URI => canonical_any_uri(literal.trim()),
+        rdf::LANG_STRING => literal
+            .rsplit_once('@')
+            .filter(|(_, lang)| is_language_tag(lang))
+            .map(|_| literal.to_string()),
+        _ => Some(literal.to_string()),
+    }
+}
+
+/// Check whether a string is a well-formed BCP 47 language tag.
+fn is_language_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag.split('-').all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+        })
+}
+
+/// Find the literals of `property_value` clauses invalid for their datatype.
+///
+/// Each invalid literal is reported as an [`Error::InvalidLiteral`] with
+/// the identifier of its frame, or without one for header clauses. A `lang`
+/// qualifier with an invalid language tag, or on a literal which is not a
+/// string, is reported with the language-tagged literal it would produce.
+pub fn check_literals<A: ForIRI>(doc: &obo::OboDoc, ctx: &mut Context<A>) -> Vec<Error> {
+    let header = doc.header().iter().filter_map(|clause| match clause {
+        obo::HeaderClause::PropertyValue(pv) => Some((None, pv.as_ref(), None)),
+        _ => None,
+    });
+    let entities = doc.entities().iter().flat_map(|entity| {
+        let id = Some(entity.as_id());
+        match entity {
+            obo::EntityFrame::Term(frame) => frame
+                .iter()
+                .filter_map(|line| match line.as_inner() {
+                    obo::TermClause::PropertyValue(pv) => {
+                        Some((id, pv.as_ref(), line.qualifiers()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            obo::EntityFrame::Typedef(frame) => frame
+                .iter()
+                .filter_map(|line| match line.as_inner() {
+                    obo::TypedefClause::PropertyValue(pv) => {
+                        Some((id, pv.as_ref(), line.qualifiers()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            obo::EntityFrame::Instance(frame) => frame
+                .iter()
+                .filter_map(|line| match line.as_inner() {
+                    obo::InstanceClause::PropertyValue(pv) => Some((id, pv.as_ref(), None)),
+                    _ => None,
+                })
+                .collect(),
+        }
+    });
+
+    let mut errors = Vec::new();
+    for (id, pv, qualifiers) in header.chain(entities) {
+        if let obo::PropertyValue::Literal(lpv) = pv {
+            let datatype: owl::IRI<A> = lpv.datatype().into_owl(ctx);
+            let literal = lpv.literal().as_str();
+            if canonicalize(datatype.as_ref(), literal).is_none() {
+                errors.push(Error::InvalidLiteral {
+                    frame: id.cloned(),
+                    literal: literal.to_string(),
+                    datatype: datatype.to_string(),
+                });
+            }
+
+            let lang = qualifiers
+                .into_iter()
+                .flat_map(|q| q.iter())
+                .find(|q| q.key() == &*LANG);
+            if let Some(lang) = lang.map(|q| q.value().as_str()) {
+                let string =
+                    is_xsd_string(ctx, lpv.datatype()) || datatype.as_ref() == rdf::LANG_STRING;
+                if !string || !is_language_tag(lang) {
+                    errors.push(Error::InvalidLiteral {
+                        frame: id.cloned(),
+                        literal: format!("{}@{}", literal, lang),
+                        datatype: datatype.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+// --- Language tags ---------------------------------------------------------
+
+/// Extract the language tag from a `lang` qualifier, if any.
+pub fn take_language(qualifiers: &mut obo::QualifierList) -> Option<String> {
+    let index = qualifiers.iter().position(|q| q.key() == &*LANG)?;
+    Some(qualifiers.remove(index).value().as_str().to_string())
+}
+
+/// Add a language tag to the string literal of an annotation.
+pub fn set_language<A: ForIRI>(ann: &mut owl::Annotation<A>, lang: String) {
+    if let owl::AnnotationValue::Literal(literal) = &mut ann.av {
+        let text = match literal {
+            owl::Literal::Simple { literal } => std::mem::take(literal),
+            owl::Literal::Datatype {
+                literal,
+                datatype_iri,
+            } if datatype_iri.as_ref() == xsd::STRING
+                || datatype_iri.as_ref() == rdf::LANG_STRING =>
+            {
+                std::mem::take(literal)
+            }
+            _ => return,
+        };
+        *literal = owl::Literal::Language {
+            literal: text,
+            lang,
+        };
+    }
+}
+
+// --- Translation -----------------------------------------------------------
+
 impl<A: ForIRI> IntoOwlCtx<A> for obo::PropertyValue {
     type Owl = owl::Annotation<A>;
     fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
@@ -30,17 +384,172 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::PropertyValue {
             },
             obo::PropertyValue::Literal(pv) => owl::Annotation {
                 ap: owl::AnnotationProperty(pv.property().into_owl(ctx)),
-                av: owl::AnnotationValue::Literal(if is_xsd_string(&ctx, pv.datatype()) {
+                av: owl::AnnotationValue::Literal(if is_xsd_string(ctx, pv.datatype()) {
                     owl::Literal::Simple {
                         literal: pv.literal().as_str().to_string(),
                     }
                 } else {
-                    owl::Literal::Datatype {
-                        datatype_iri: pv.datatype().into_owl(ctx),
-                        literal: pv.literal().as_str().to_string(),
+                    let datatype_iri: owl::IRI<A> = pv.datatype().into_owl(ctx);
+                    let literal = pv.literal().as_str();
+                    match canonicalize(datatype_iri.as_ref(), literal) {
+                        // `"text@lang" rdf:langString` becomes a language-tagged literal
+                        Some(canonical) if datatype_iri.as_ref() == rdf::LANG_STRING => {
+                            match canonical.rsplit_once('@') {
+                                Some((text, lang)) => owl::Literal::Language {
+                                    literal: text.to_string(),
+                                    lang: lang.to_string(),
+                                },
+                                None => owl::Literal::Datatype {
+                                    datatype_iri,
+                                    literal: canonical,
+                                },
+                            }
+                        }
+                        // valid literals are replaced with their canonical form
+                        Some(canonical) => owl::Literal::Datatype {
+                            datatype_iri,
+                            literal: canonical,
+                        },
+                        // invalid literals are kept as-is
+                        None => owl::Literal::Datatype {
+                            datatype_iri,
+                            literal: literal.to_string(),
+                        },
                     }
                 }),
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn canonicalize_integer() {
+        assert_eq!(canonicalize(xsd::INTEGER, "+0012").as_deref(), Some("12"));
+        assert_eq!(canonicalize(xsd::INTEGER, "-0").as_deref(), Some("0"));
+        assert_eq!(canonicalize(xsd::INTEGER, " -42 ").as_deref(), Some("-42"));
+        assert_eq!(canonicalize(xsd::INTEGER, "1.5"), None);
+        assert_eq!(canonicalize(xsd::INTEGER, "+"), None);
+    }
+
+    #[test]
+    fn canonicalize_decimal() {
+        assert_eq!(
+            canonicalize(xsd::DECIMAL, "001.500").as_deref(),
+            Some("1.5")
+        );
+        assert_eq!(canonicalize(xsd::DECIMAL, "2.0").as_deref(), Some("2"));
+        assert_eq!(canonicalize(xsd::DECIMAL, "-.50").as_deref(), Some("-0.5"));
+        assert_eq!(canonicalize(xsd::DECIMAL, "-0.0").as_deref(), Some("0"));
+        assert_eq!(canonicalize(xsd::DECIMAL, "."), None);
+        assert_eq!(canonicalize(xsd::DECIMAL, "1e5"), None);
+    }
+
+    #[test]
+    fn canonicalize_boolean() {
+        assert_eq!(canonicalize(xsd::BOOLEAN, "1").as_deref(), Some("true"));
+        assert_eq!(
+            canonicalize(xsd::BOOLEAN, "false").as_deref(),
+            Some("false")
+        );
+        assert_eq!(canonicalize(xsd::BOOLEAN, "yes"), None);
+    }
+
+    #[test]
+    fn canonicalize_datetime() {
+        assert_eq!(
+            canonicalize(xsd::DATETIME, "2019-01-31T12:30:00.500+00:00").as_deref(),
+            Some("2019-01-31T12:30:00.5Z")
+        );
+        assert_eq!(
+            canonicalize(xsd::DATETIME, "2019-01-31T12:30:00-05:00").as_deref(),
+            Some("2019-01-31T12:30:00-05:00")
+        );
+        assert_eq!(canonicalize(xsd::DATETIME, "2019-13-01T00:00:00"), None);
+        assert_eq!(canonicalize(xsd::DATETIME, "2019-04-31T00:00:00"), None);
+        assert_eq!(
+            canonicalize(xsd::DATETIME, "2019-12-31T24:00:00Z").as_deref(),
+            Some("2020-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            canonicalize(xsd::DATETIME, "2020-02-28T24:00:00").as_deref(),
+            Some("2020-02-29T00:00:00")
+        );
+        assert_eq!(canonicalize(xsd::DATETIME, "2019-01-31T24:00:01"), None);
+        assert_eq!(canonicalize(xsd::DATETIME, "2019-01-31"), None);
+        assert_eq!(
+            canonicalize(xsd::DATE, "2019-01-31").as_deref(),
+            Some("2019-01-31")
+        );
+    }
+
+    #[test]
+    fn canonicalize_date() {
+        assert_eq!(
+            canonicalize(xsd::DATE, "2020-02-29").as_deref(),
+            Some("2020-02-29")
+        );
+        assert_eq!(
+            canonicalize(xsd::DATE, "2000-02-29Z").as_deref(),
+            Some("2000-02-29Z")
+        );
+        assert_eq!(canonicalize(xsd::DATE, "2021-02-29"), None);
+        assert_eq!(canonicalize(xsd::DATE, "1900-02-29"), None);
+        assert_eq!(canonicalize(xsd::DATE, "2021-02-31"), None);
+        assert_eq!(canonicalize(xsd::DATE, "2021-06-31"), None);
+        assert_eq!(canonicalize(xsd::DATE, "2021-06-00"), None);
+    }
+
+    #[test]
+    fn check_literals_frames() {
+        let doc = fastobo::from_str(
+            "ontology: test\nproperty_value: IAO:0000001 \"x\" xsd:boolean\n\n[Term]\nid: TST:01\nproperty_value: IAO:0000002 \"1.5\" xsd:integer\nproperty_value: IAO:0000003 \"2\" xsd:integer\n",
+        )
+        .unwrap();
+        let mut ctx = Context::<String>::from_obodoc(&doc).unwrap();
+        let frames: Vec<_> = check_literals(&doc, &mut ctx)
+            .into_iter()
+            .map(|error| match error {
+                Error::InvalidLiteral { frame, .. } => frame.map(|id| id.to_string()),
+                other => panic!("unexpected error: {}", other),
+            })
+            .collect();
+        assert_eq!(frames, vec![None, Some(String::from("TST:01"))]);
+    }
+
+    #[test]
+    fn check_literals_lang() {
+        let doc = fastobo::from_str(
+            "ontology: test\n\n[Term]\nid: TST:01\nproperty_value: IAO:0000001 \"a\" xsd:string {lang=\"en\"}\nproperty_value: IAO:0000002 \"1\" xsd:integer {lang=\"en\"}\nproperty_value: IAO:0000003 \"b\" xsd:string {lang=\"en_GB\"}\n",
+        )
+        .unwrap();
+        let mut ctx = Context::<String>::from_obodoc(&doc).unwrap();
+        let literals: Vec<_> = check_literals(&doc, &mut ctx)
+            .into_iter()
+            .map(|error| match error {
+                Error::InvalidLiteral { literal, .. } => literal,
+                other => panic!("unexpected error: {}", other),
+            })
+            .collect();
+        assert_eq!(literals, vec!["1@en", "b@en_GB"]);
+    }
+
+    #[test]
+    fn canonicalize_any_uri() {
+        assert_eq!(
+            canonicalize(xsd::ANY_URI, " http://example.com ").as_deref(),
+            Some("http://example.com")
+        );
+        assert_eq!(canonicalize(xsd::ANY_URI, "http://a b"), None);
+    }
+
+    #[test]
+    fn canonicalize_lang_string() {
+        assert!(canonicalize(rdf::LANG_STRING, "chat@fr").is_some());
+        assert!(canonicalize(rdf::LANG_STRING, "chat").is_none());
+    }
+}
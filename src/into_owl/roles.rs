@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use fastobo::ast as obo;
+use horned_owl::model as owl;
+use horned_owl::model::ForIRI;
+use horned_owl::visitor::immutable::Visit;
+use horned_owl::visitor::immutable::Walk;
+
+use super::declare::is_reserved;
+use crate::error::Role;
+use crate::error::RoleConflict;
+
+/// A visitor recording the roles in which IRIs are used by a component.
+#[derive(Debug)]
+struct Collector<A: ForIRI> {
+    uses: BTreeSet<(owl::IRI<A>, Role)>,
+    declared: BTreeSet<(owl::IRI<A>, Role)>,
+}
+
+impl<A: ForIRI> Collector<A> {
+    fn use_entity(&mut self, iri: &owl::IRI<A>, role: Role) {
+        if !is_reserved(iri) {
+            self.uses.insert((iri.clone(), role));
+        }
+    }
+}
+
+impl<A: ForIRI> Visit<A> for Collector<A> {
+    fn visit_component(&mut self, c: &owl::Component<A>) {
+        let declared = match c {
+            owl::Component::DeclareClass(d) => (d.0 .0.clone(), Role::Class),
+            owl::Component::DeclareDatatype(d) => (d.0 .0.clone(), Role::Datatype),
+            owl::Component::DeclareObjectProperty(d) => (d.0 .0.clone(), Role::ObjectProperty),
+            owl::Component::DeclareDataProperty(d) => (d.0 .0.clone(), Role::DataProperty),
+            owl::Component::DeclareAnnotationProperty(d) => {
+                (d.0 .0.clone(), Role::AnnotationProperty)
+            }
+            owl::Component::DeclareNamedIndividual(d) => (d.0 .0.clone(), Role::NamedIndividual),
+            _ => return,
+        };
+        self.declared.insert(declared);
+    }
+
+    fn visit_class(&mut self, c: &owl::Class<A>) {
+        self.use_entity(&c.0, Role::Class);
+    }
+
+    fn visit_datatype(&mut self, dt: &owl::Datatype<A>) {
+        self.use_entity(&dt.0, Role::Datatype);
+    }
+
+    fn visit_object_property(&mut self, op: &owl::ObjectProperty<A>) {
+        self.use_entity(&op.0, Role::ObjectProperty);
+    }
+
+    fn visit_data_property(&mut self, dp: &owl::DataProperty<A>) {
+        self.use_entity(&dp.0, Role::DataProperty);
+    }
+
+    fn visit_annotation_property(&mut self, ap: &owl::AnnotationProperty<A>) {
+        self.use_entity(&ap.0, Role::AnnotationProperty);
+    }
+
+    fn visit_named_individual(&mut self, i: &owl::NamedIndividual<A>) {
+        self.use_entity(&i.0, Role::NamedIndividual);
+    }
+}
+
+/// Record the roles used and declared by a single component.
+fn collect<A: ForIRI>(component: &owl::AnnotatedComponent<A>) -> Collector<A> {
+    let mut walk = Walk::new(Collector {
+        uses: BTreeSet::new(),
+        declared: BTreeSet::new(),
+    });
+    walk.annotated_component(component);
+    walk.into_visit()
+}
+
+/// A checker for IRIs used in incompatible roles in the produced ontology.
+///
+/// OWL 2 DL allows an IRI to be used both as a class and as an individual,
+/// but not as two different kinds of properties, nor as both a class and
+/// a datatype. Since the translation of a typedef depends on its own frame
+/// (e.g. metadata tags become annotation properties), such conflicts can
+/// appear when a typedef is used in a clause expecting another kind of
+/// property.
+#[derive(Debug)]
+pub struct Roles<A: ForIRI> {
+    uses: BTreeMap<owl::IRI<A>, BTreeMap<Role, BTreeSet<obo::Ident>>>,
+    declared: BTreeMap<owl::IRI<A>, BTreeSet<Role>>,
+}
+
+impl<A: ForIRI> Roles<A> {
+    /// Create a new checker with no component recorded.
+    pub fn new() -> Self {
+        Self {
+            uses: BTreeMap::new(),
+            declared: BTreeMap::new(),
+        }
+    }
+
+    /// Record the roles of the entities in a component of the given frame.
+    pub fn visit(&mut self, frame: Option<&obo::Ident>, component: &owl::AnnotatedComponent<A>) {
+        let collector = collect(component);
+        for (iri, role) in collector.uses {
+            let frames = self.uses.entry(iri).or_default().entry(role).or_default();
+            frames.extend(frame.cloned());
+        }
+        for (iri, role) in collector.declared {
+            self.declared.entry(iri).or_default().insert(role);
+        }
+    }
+
+    /// Get all the IRIs used in conflicting roles.
+    pub fn conflicts(&self) -> Vec<RoleConflict> {
+        let mut conflicts = Vec::new();
+        for (iri, uses) in self.uses.iter() {
+            let conflicting = uses
+                .keys()
+                .any(|r1| uses.keys().any(|r2| r1.conflicts_with(*r2)));
+            if conflicting {
+                let frames = uses.values().flatten().cloned().collect::<BTreeSet<_>>();
+                conflicts.push(RoleConflict {
+                    iri: iri.to_string(),
+                    roles: uses.keys().cloned().collect(),
+                    frames: frames.into_iter().collect(),
+                });
+            }
+        }
+        conflicts
+    }
+
+    /// Get the uses of IRIs in conflicting roles, which must be removed.
+    ///
+    /// A declared role is always kept, and other roles are removed if they
+    /// conflict with a declared role or with another role of the same IRI.
+    /// This means all the conflicting uses of an undeclared IRI are removed.
+    pub fn conflicting_uses(&self) -> BTreeSet<(owl::IRI<A>, Role)> {
+        let mut conflicting = BTreeSet::new();
+        let undeclared = BTreeSet::new();
+        for (iri, uses) in self.uses.iter() {
+            let declared = self.declared.get(iri).unwrap_or(&undeclared);
+            for role in uses.keys() {
+                let conflicts = declared
+                    .iter()
+                    .chain(uses.keys())
+                    .any(|other| role.conflicts_with(*other));
+                if conflicts && !declared.contains(role) {
+                    conflicting.insert((iri.clone(), *role));
+                }
+            }
+        }
+        conflicting
+    }
+}
+
+/// Check whether a component has any of the given conflicting uses.
+pub fn is_conflicting<A: ForIRI>(
+    conflicting: &BTreeSet<(owl::IRI<A>, Role)>,
+    component: &owl::AnnotatedComponent<A>,
+) -> bool {
+    collect(component)
+        .uses
+        .iter()
+        .any(|u| conflicting.contains(u))
+}
+
+impl<A: ForIRI> Default for Roles<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use horned_owl::ontology::set::SetOntology;
+
+    use super::*;
+    use crate::Config;
+    use crate::Error;
+    use crate::IntoOwl;
+    use crate::RolePolicy;
+
+    #[test]
+    fn conflicts_report_frames() {
+        let doc = fastobo::from_str(
+            "ontology: test\ndefault-namespace: test\n\n[Term]\nid: TST:001\nintersection_of: TST:003\nintersection_of: TST:002 TST:004\n\n[Typedef]\nid: TST:002\nis_metadata_tag: true\n",
+        )
+        .unwrap();
+        let config = Config {
+            role_conflicts: RolePolicy::Error,
+            ..Config::default()
+        };
+        match doc.into_owl_with_config::<SetOntology<String>>(config) {
+            Err(Error::RoleConflicts(conflicts)) => {
+                assert_eq!(conflicts.len(), 1);
+                assert_eq!(conflicts[0].iri, "http://purl.obolibrary.org/obo/TST_002");
+                assert_eq!(
+                    conflicts[0].roles,
+                    vec![Role::ObjectProperty, Role::AnnotationProperty]
+                );
+                assert_eq!(
+                    conflicts[0].frames,
+                    vec![
+                        obo::Ident::from(obo::PrefixedIdent::new("TST", "001")),
+                        obo::Ident::from(obo::PrefixedIdent::new("TST", "002")),
+                    ]
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
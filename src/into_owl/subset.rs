@@ -0,0 +1,189 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use fastobo::ast as obo;
+
+/// Reduce an OBO document to the terms belonging to the given subsets.
+///
+/// Terms that are not members of any of the `subsets` are removed, as well
+/// as the clauses of member terms that reference removed terms. Typedefs are
+/// only kept if they are used by a remaining `relationship`,
+/// `intersection_of` or `property_value` clause, or by the header or another
+/// kept typedef. When `closure` is `true`, `is_a` clauses
+/// pointing to a removed term are replaced with `is_a` clauses pointing to
+/// its closest ancestors in the subset.
+pub fn extract_subset(doc: &mut obo::OboDoc, subsets: &[obo::SubsetIdent], closure: bool) {
+    // Collect the members of the subsets, and the superclasses of all terms.
+    let mut members: HashSet<obo::ClassIdent> = HashSet::new();
+    let mut parents: HashMap<obo::ClassIdent, Vec<obo::ClassIdent>> = HashMap::new();
+    for frame in doc.entities().iter().flat_map(obo::EntityFrame::as_term) {
+        let id = frame.id().as_inner();
+        for line in frame.iter() {
+            match line.as_inner() {
+                obo::TermClause::Subset(s) if subsets.contains(s) => {
+                    members.insert(id.clone());
+                }
+                obo::TermClause::IsA(sup) => {
+                    parents
+                        .entry(id.clone())
+                        .or_default()
+                        .push(sup.as_ref().clone());
+                }
+                _ => (),
+            }
+        }
+    }
+
+    // Remove non-member terms.
+    doc.entities_mut().retain(|entity| match entity {
+        obo::EntityFrame::Term(frame) => members.contains(frame.id().as_inner()),
+        _ => true,
+    });
+
+    // Remove dangling clauses and record the relations in use.
+    let mut relations: HashSet<obo::RelationIdent> = HashSet::new();
+    for frame in doc.entities_mut().iter_mut() {
+        if let obo::EntityFrame::Term(frame) = frame {
+            // Find the closest member ancestors of removed superclasses.
+            let mut bridges = BTreeSet::new();
+            if closure {
+                for line in frame.iter() {
+                    if let obo::TermClause::IsA(sup) = line.as_inner() {
+                        if !members.contains(sup) {
+                            bridges.extend(closest_members(sup, &members, &parents));
+                        }
+                    }
+                }
+            }
+
+            // Remove clauses referencing terms outside of the subsets; if
+            // any part of a logical definition is removed, then the whole
+            // definition is removed, since it would be changed otherwise.
+            let mut logical_definition = true;
+            frame.retain(|line| match line.as_inner() {
+                obo::TermClause::IsA(cid)
+                | obo::TermClause::EquivalentTo(cid)
+                | obo::TermClause::DisjointFrom(cid)
+                | obo::TermClause::Relationship(_, cid) => members.contains(cid),
+                obo::TermClause::IntersectionOf(_, cid) | obo::TermClause::UnionOf(cid) => {
+                    logical_definition &= members.contains(cid);
+                    true
+                }
+                _ => true,
+            });
+            if !logical_definition {
+                frame.retain(|line| {
+                    !matches!(
+                        line.as_inner(),
+                        obo::TermClause::IntersectionOf(_, _) | obo::TermClause::UnionOf(_)
+                    )
+                });
+            }
+
+            // Add the bridging `is_a` clauses not already in the frame.
+            for sup in bridges {
+                let clause = obo::TermClause::IsA(Box::new(sup));
+                if !frame.iter().any(|line| line.as_inner() == &clause) {
+                    frame.push(obo::Line::from(clause));
+                }
+            }
+
+            // Record the relations used by the remaining clauses.
+            for line in frame.iter() {
+                match line.as_inner() {
+                    obo::TermClause::Relationship(rid, _)
+                    | obo::TermClause::IntersectionOf(Some(rid), _) => {
+                        relations.insert(rid.as_ref().clone());
+                    }
+                    obo::TermClause::PropertyValue(pv) => {
+                        relations.insert(pv.property().clone());
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    // Record the relations used in header property values.
+    for clause in doc.header().iter() {
+        if let obo::HeaderClause::PropertyValue(pv) = clause {
+            relations.insert(pv.property().clone());
+        }
+    }
+
+    // Record the relations used by the typedefs in use, transitively.
+    let typedefs: HashMap<&obo::RelationIdent, &obo::TypedefFrame> = doc
+        .entities()
+        .iter()
+        .flat_map(obo::EntityFrame::as_typedef)
+        .map(|frame| (frame.id().as_inner(), frame))
+        .collect();
+    let mut queue: Vec<obo::RelationIdent> = relations.iter().cloned().collect();
+    while let Some(rid) = queue.pop() {
+        if let Some(frame) = typedefs.get(&rid) {
+            for r in referenced_relations(frame) {
+                if relations.insert(r.clone()) {
+                    queue.push(r.clone());
+                }
+            }
+        }
+    }
+
+    // Remove the typedefs that are not referenced by member terms.
+    doc.entities_mut().retain(|entity| match entity {
+        obo::EntityFrame::Typedef(frame) => relations.contains(frame.id().as_inner()),
+        _ => true,
+    });
+}
+
+/// Get the relations referenced by the clauses of a typedef frame.
+fn referenced_relations(frame: &obo::TypedefFrame) -> Vec<&obo::RelationIdent> {
+    let mut relations = Vec::new();
+    for line in frame.iter() {
+        match line.as_inner() {
+            obo::TypedefClause::IsA(rid)
+            | obo::TypedefClause::IntersectionOf(rid)
+            | obo::TypedefClause::UnionOf(rid)
+            | obo::TypedefClause::EquivalentTo(rid)
+            | obo::TypedefClause::DisjointFrom(rid)
+            | obo::TypedefClause::InverseOf(rid)
+            | obo::TypedefClause::TransitiveOver(rid)
+            | obo::TypedefClause::DisjointOver(rid)
+            | obo::TypedefClause::ReplacedBy(rid) => relations.push(rid.as_ref()),
+            obo::TypedefClause::HoldsOverChain(r1, r2)
+            | obo::TypedefClause::EquivalentToChain(r1, r2)
+            | obo::TypedefClause::Relationship(r1, r2) => {
+                relations.push(r1.as_ref());
+                relations.push(r2.as_ref());
+            }
+            obo::TypedefClause::PropertyValue(pv) => relations.push(pv.property()),
+            _ => (),
+        }
+    }
+    relations
+}
+
+/// Find the closest ancestors of a class that are members of the subsets.
+fn closest_members(
+    cls: &obo::ClassIdent,
+    members: &HashSet<obo::ClassIdent>,
+    parents: &HashMap<obo::ClassIdent, Vec<obo::ClassIdent>>,
+) -> Vec<obo::ClassIdent> {
+    let mut found = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(cls);
+    while let Some(current) = queue.pop_front() {
+        if !visited.insert(current) {
+            continue;
+        }
+        if members.contains(current) {
+            found.push(current.clone());
+        } else if let Some(sups) = parents.get(current) {
+            queue.extend(sups.iter());
+        }
+    }
+    found
+}
@@ -1,14 +1,83 @@
 use fastobo::ast as obo;
+use fastobo::semantics::Identified;
 use horned_owl::model as owl;
 use horned_owl::model::ForIRI;
 
 use super::Context;
 use super::IntoOwlCtx;
+use crate::config::SynonymTypePolicy;
 use crate::constants::property;
+use crate::error::Error;
+use crate::error::FrameError;
+
+/// Check that all synonyms are consistent with the declared synonym types.
+///
+/// The type of every synonym must be declared with a `synonymtypedef`
+/// header clause, and synonyms must have the scope declared by their type,
+/// if any. All the inconsistent synonyms are reported, in document order.
+pub fn check_synonyms<A: ForIRI>(doc: &obo::OboDoc, ctx: &Context<A>) -> Vec<Error> {
+    let mut errors = Vec::new();
+    for entity in doc.entities() {
+        let synonyms: Vec<&obo::Synonym> = match entity {
+            obo::EntityFrame::Term(frame) => frame
+                .iter()
+                .filter_map(|line| match line.as_inner() {
+                    obo::TermClause::Synonym(syn) => Some(syn.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+            obo::EntityFrame::Typedef(frame) => frame
+                .iter()
+                .filter_map(|line| match line.as_inner() {
+                    obo::TypedefClause::Synonym(syn) => Some(syn.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+            obo::EntityFrame::Instance(frame) => frame
+                .iter()
+                .filter_map(|line| match line.as_inner() {
+                    obo::InstanceClause::Synonym(syn) => Some(syn.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+        };
+
+        for syn in synonyms {
+            let ty = match syn.ty() {
+                Some(ty) => ty,
+                None => continue,
+            };
+            let error = match ctx.synonym_types.get(ty) {
+                None => FrameError::UndeclaredSynonymType { ty: ty.clone() },
+                Some(Some(scope)) if scope != syn.scope() => FrameError::SynonymScopeMismatch {
+                    ty: ty.clone(),
+                    expected: scope.clone(),
+                    found: syn.scope().clone(),
+                },
+                Some(_) => continue,
+            };
+            errors.push(Error::InvalidFrame {
+                id: entity.as_id().clone(),
+                error,
+            });
+        }
+    }
+    errors
+}
 
 impl<A: ForIRI> IntoOwlCtx<A> for obo::Synonym {
     type Owl = owl::AnnotatedComponent<A>;
     fn into_owl(mut self, ctx: &mut Context<A>) -> Self::Owl {
+        // Use the scope of the synonym type, and drop undeclared types.
+        let mut ty = self.ty().cloned();
+        if ctx.config.synonym_types == SynonymTypePolicy::Repair {
+            match ty.as_ref().map(|t| ctx.synonym_types.get(t)) {
+                Some(None) => ty = None,
+                Some(Some(Some(scope))) => *self.scope_mut() = scope.clone(),
+                _ => (),
+            }
+        }
+
         // Build the main assertion
         let axiom = owl::AnnotationAssertion {
             subject: owl::AnnotationSubject::from(&ctx.current_frame),
@@ -18,14 +87,13 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::Synonym {
             },
         };
 
-        let mut annotations =
-            std::mem::replace(self.xrefs_mut(), obo::XrefList::default()).into_owl(ctx);
-        if let Some(ty) = self.ty() {
+        let mut annotations = std::mem::take(self.xrefs_mut()).into_owl(ctx);
+        if let Some(ty) = ty {
             annotations.insert(owl::Annotation {
                 ap: ctx
                     .build
                     .annotation_property(property::obo_in_owl::HAS_SYNONYM_TYPE),
-                av: owl::AnnotationValue::IRI(ty.into_owl(ctx)),
+                av: owl::AnnotationValue::IRI((&ty).into_owl(ctx)),
             });
         }
 
@@ -44,3 +112,109 @@ impl<A: ForIRI> IntoOwlCtx<A> for &obo::SynonymScope {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::config::Config;
+
+    const DOC: &str = "ontology: test
+synonymtypedef: systematic \"Systematic synonym\" EXACT
+synonymtypedef: common \"Common name\"
+
+[Term]
+id: TST:01
+";
+
+    fn context(synonym_types: SynonymTypePolicy, doc: &obo::OboDoc) -> Context<String> {
+        let config = Config {
+            synonym_types,
+            ..Default::default()
+        };
+        let mut ctx = Context::with_config(doc, config).unwrap();
+        ctx.current_frame = ctx.build.iri("http://purl.obolibrary.org/obo/TST_01");
+        ctx
+    }
+
+    fn check(synonym: &str) -> Result<(), Error> {
+        let doc = fastobo::from_str(format!("{}synonym: {}\n", DOC, synonym)).unwrap();
+        match check_synonyms(&doc, &context(SynonymTypePolicy::Error, &doc))
+            .into_iter()
+            .next()
+        {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn repair(synonym: &str) -> owl::AnnotatedComponent<String> {
+        let doc = fastobo::from_str(DOC).unwrap();
+        let mut ctx = context(SynonymTypePolicy::Repair, &doc);
+        let line = format!("[Term]\nid: TST:01\nsynonym: {}\n", synonym);
+        let frame = fastobo::from_str(format!("ontology: test\n\n{}", line)).unwrap();
+        match frame.entities()[0].as_term().unwrap()[0].as_inner() {
+            obo::TermClause::Synonym(syn) => syn.as_ref().clone().into_owl(&mut ctx),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn check_declared() {
+        assert!(check("\"x\" EXACT systematic []").is_ok());
+        assert!(check("\"x\" BROAD common []").is_ok());
+        assert!(check("\"x\" BROAD []").is_ok());
+    }
+
+    #[test]
+    fn check_undeclared() {
+        assert!(matches!(
+            check("\"x\" EXACT unknown []"),
+            Err(Error::InvalidFrame {
+                error: FrameError::UndeclaredSynonymType { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn check_scope_mismatch() {
+        assert!(matches!(
+            check("\"x\" BROAD systematic []"),
+            Err(Error::InvalidFrame {
+                error: FrameError::SynonymScopeMismatch {
+                    expected: obo::SynonymScope::Exact,
+                    found: obo::SynonymScope::Broad,
+                    ..
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn repair_scope() {
+        let axiom = repair("\"x\" BROAD systematic []");
+        match axiom.component {
+            owl::Component::AnnotationAssertion(assertion) => assert_eq!(
+                &*assertion.ann.ap.0,
+                property::obo_in_owl::HAS_EXACT_SYNONYM
+            ),
+            _ => unreachable!(),
+        }
+        assert_eq!(axiom.ann.len(), 1);
+    }
+
+    #[test]
+    fn repair_undeclared() {
+        let axiom = repair("\"x\" BROAD unknown []");
+        match axiom.component {
+            owl::Component::AnnotationAssertion(assertion) => assert_eq!(
+                &*assertion.ann.ap.0,
+                property::obo_in_owl::HAS_BROAD_SYNONYM
+            ),
+            _ => unreachable!(),
+        }
+        assert!(axiom.ann.is_empty());
+    }
+}
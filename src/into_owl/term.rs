@@ -1,94 +1,346 @@
 use std::collections::BTreeSet;
+use std::collections::HashSet;
 use std::iter::FromIterator;
 
 use fastobo::ast as obo;
+use fastobo::semantics::Identified;
+use fastobo::semantics::OboFrame;
 use horned_owl::model as owl;
 use horned_owl::model::ForIRI;
 
+use super::clause_tag;
 use super::Context;
+use super::FrameAxioms;
 use super::IntoOwlCtx;
+use crate::config::ObsoletionPolicy;
 use crate::constants::datatype;
 use crate::constants::property;
+use crate::error::Error;
+use crate::error::FrameError;
+
+/// Check that a term frame can be translated according to the OBO 1.4 semantics.
+///
+/// This checks the cardinality of all clauses (so that `intersection_of` and
+/// `union_of` clauses appear either zero or at least two times, and clauses
+/// such as `is_anonymous` at most once), and that the frame is not defined
+/// both as a union and as an intersection, nor with differentia but no genus.
+/// The cardinality qualifiers of `relationship` and `intersection_of` clauses
+/// must also have a non-negative integer value.
+pub fn validate(frame: &obo::TermFrame) -> Result<(), Error> {
+    let error = |error: FrameError| Error::InvalidFrame {
+        id: frame.id().as_inner().as_ref().clone(),
+        error,
+    };
+
+    frame
+        .cardinality_check()
+        .map_err(|e| error(FrameError::from(e)))?;
+
+    let has_intersection = frame
+        .iter()
+        .any(|line| matches!(line.as_inner(), obo::TermClause::IntersectionOf(_, _)));
+    let has_union = frame
+        .iter()
+        .any(|line| matches!(line.as_inner(), obo::TermClause::UnionOf(_)));
+    if has_intersection && has_union {
+        return Err(error(FrameError::MixedDefinitions));
+    }
 
-impl<A: ForIRI> IntoOwlCtx<A> for obo::TermFrame {
-    type Owl = BTreeSet<owl::AnnotatedComponent<A>>;
-    fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
-        // The ID of this frame translated to an IRI.
-        let id = self.id().clone().into_inner().into_owl(ctx);
+    let (mut has_genus, mut has_differentia) = (false, false);
+    for line in frame.iter() {
+        match line.as_inner() {
+            obo::TermClause::IntersectionOf(None, _) => has_genus = true,
+            obo::TermClause::IntersectionOf(Some(_), _) => has_differentia = true,
+            _ => (),
+        }
+    }
+    if has_differentia && !has_genus {
+        return Err(error(FrameError::MissingGenus));
+    }
 
-        // The translated axioms.
-        let mut axioms: Self::Owl = BTreeSet::new();
+    let keys = [
+        &*super::CARDINALITY,
+        &*super::MIN_CARDINALITY,
+        &*super::MAX_CARDINALITY,
+    ];
+    for line in frame.iter() {
+        match line.as_inner() {
+            obo::TermClause::Relationship(_, _) | obo::TermClause::IntersectionOf(Some(_), _) => (),
+            _ => continue,
+        }
+        let qualifiers = line.qualifiers().into_iter().flat_map(|q| q.iter());
+        for q in qualifiers.filter(|q| keys.contains(&q.key())) {
+            if q.value().parse::<u32>().is_err() {
+                return Err(error(FrameError::InvalidQualifier {
+                    key: q.key().to_string(),
+                    value: q.value().to_string(),
+                }));
+            }
+        }
+    }
 
-        // Build the annotated class declaration.
-        axioms.insert(owl::AnnotatedComponent {
-            ann: BTreeSet::new(),
-            component: owl::Component::from(owl::DeclareClass(owl::Class(id.clone()))),
-        });
-
-        // Add the original OBO ID as an annotation.
-        // FIXME: maybe only do that if the term is not declared as being
-        //        anonymous ?
-        axioms.insert(owl::AnnotatedComponent::from(owl::AnnotationAssertion {
-            subject: owl::AnnotationSubject::from(&id),
-            ann: owl::Annotation {
-                ap: ctx.build.annotation_property(property::obo_in_owl::ID),
-                av: owl::AnnotationValue::Literal(owl::Literal::Simple {
-                    literal: self.id().as_ref().to_string(),
-                }),
-            },
-        }));
+    Ok(())
+}
+
+/// Check that the replacements of obsolete terms are declared in the document.
+///
+/// The targets of the `replaced_by` and `consider` clauses of obsolete terms
+/// must be the identifier of an entity frame of the document.
+pub fn check_replacements(doc: &obo::OboDoc) -> Result<(), Error> {
+    let ids: HashSet<&obo::Ident> = doc.entities().iter().map(|e| e.as_id()).collect();
+    for frame in doc.entities().iter().flat_map(obo::EntityFrame::as_term) {
+        if !is_obsolete(frame) {
+            continue;
+        }
+        for line in frame.iter() {
+            let target = match line.as_inner() {
+                obo::TermClause::ReplacedBy(id) => id.as_ref().as_ref(),
+                obo::TermClause::Consider(id) => id.as_ref().as_ref(),
+                _ => continue,
+            };
+            if !ids.contains(target) {
+                return Err(Error::InvalidFrame {
+                    id: frame.id().as_inner().as_ref().clone(),
+                    error: FrameError::MissingReplacement {
+                        target: target.clone(),
+                    },
+                });
+            }
+        }
+    }
+    Ok(())
+}
 
-        // Group some assertion clauses together
-        let mut intersections: Vec<owl::ClassExpression<A>> = Vec::new();
-        let mut intersections_a: BTreeSet<owl::Annotation<A>> = BTreeSet::new();
-        let mut unions: Vec<owl::ClassExpression<A>> = Vec::new();
-        let mut unions_a: BTreeSet<owl::Annotation<A>> = BTreeSet::new();
+/// Check whether a term frame is declared as obsolete.
+fn is_obsolete(frame: &obo::TermFrame) -> bool {
+    frame
+        .iter()
+        .any(|line| matches!(line.as_inner(), obo::TermClause::IsObsolete(true)))
+}
 
-        // Convert remaining clauses to axioms.
-        for line in self.into_iter() {
-            if let Some(mut ac) = line.into_owl(ctx) {
-                if let owl::Component::EquivalentClasses(eq) = &ac.component {
-                    match &eq.0[1] {
-                        owl::ClassExpression::ObjectIntersectionOf(objs) => {
-                            intersections.append(&mut objs.clone());
-                            intersections_a.append(&mut ac.ann);
-                        }
-                        owl::ClassExpression::ObjectUnionOf(objs) => {
-                            unions.append(&mut objs.clone());
-                            unions_a.append(&mut ac.ann);
-                        }
-                        _ => {
-                            axioms.insert(ac);
-                        }
-                    }
-                } else {
-                    axioms.insert(ac);
-                }
+/// A builder for the logical definition of a class.
+///
+/// The `intersection_of` and `union_of` clauses of a term frame are
+/// collected and combined into `EquivalentClasses` axioms once the whole
+/// frame has been processed:
+///
+/// * all `intersection_of` clauses are grouped into a single
+///   `ObjectIntersectionOf` expression, with the genus classes first and
+///   the differentia afterwards, both in the order of the frame;
+/// * all `union_of` clauses are grouped into a single `ObjectUnionOf`
+///   expression, in the order of the frame.
+///
+/// The `equivalent_to` clauses are not grouped: each of them is translated
+/// to its own `EquivalentClasses` axiom like any other clause.
+///
+/// The qualifiers of the grouped clauses are merged into the annotations
+/// of the resulting axiom.
+#[derive(Debug)]
+pub struct DefinitionBuilder<A: ForIRI> {
+    genus: Vec<owl::ClassExpression<A>>,
+    differentia: Vec<owl::ClassExpression<A>>,
+    intersection_ann: BTreeSet<owl::Annotation<A>>,
+    unions: Vec<owl::ClassExpression<A>>,
+    union_ann: BTreeSet<owl::Annotation<A>>,
+}
+
+impl<A: ForIRI> DefinitionBuilder<A> {
+    /// Create a new builder for an empty logical definition.
+    pub fn new() -> Self {
+        Self {
+            genus: Vec::new(),
+            differentia: Vec::new(),
+            intersection_ann: BTreeSet::new(),
+            unions: Vec::new(),
+            union_ann: BTreeSet::new(),
+        }
+    }
+
+    /// Add a genus class to the intersection.
+    pub fn add_genus(&mut self, cls: owl::ClassExpression<A>, ann: BTreeSet<owl::Annotation<A>>) {
+        self.genus.push(cls);
+        self.intersection_ann.extend(ann);
+    }
+
+    /// Add a differentia class expression to the intersection.
+    pub fn add_differentia(
+        &mut self,
+        expr: owl::ClassExpression<A>,
+        ann: BTreeSet<owl::Annotation<A>>,
+    ) {
+        self.differentia.push(expr);
+        self.intersection_ann.extend(ann);
+    }
+
+    /// Add an operand to the union.
+    pub fn add_union(&mut self, cls: owl::ClassExpression<A>, ann: BTreeSet<owl::Annotation<A>>) {
+        self.unions.push(cls);
+        self.union_ann.extend(ann);
+    }
+
+    /// Add a clause to the definition if it is a logical definition clause.
+    ///
+    /// Returns the line unchanged if it is not part of a logical definition.
+    pub fn push(
+        &mut self,
+        mut line: obo::Line<obo::TermClause>,
+        ctx: &mut Context<A>,
+    ) -> Option<obo::Line<obo::TermClause>> {
+        if !matches!(
+            line.as_inner(),
+            obo::TermClause::IntersectionOf(_, _) | obo::TermClause::UnionOf(_)
+        ) {
+            return Some(line);
+        }
+
+        let qualifiers = match line.qualifiers_mut() {
+            Some(q) => std::mem::take(q),
+            None => obo::QualifierList::default(),
+        };
+        let mut ann = ctx.line_comment(&line);
+        match line.into_inner() {
+            obo::TermClause::IntersectionOf(Some(rid), cid) => {
+                let expr = ctx.rel_class_expression(&qualifiers, *rid, *cid);
+                ann.append(&mut qualifiers.into_owl(ctx));
+                self.add_differentia(expr, ann);
             }
+            obo::TermClause::IntersectionOf(None, cid) => {
+                let cls = owl::ClassExpression::from(owl::Class(cid.into_owl(ctx)));
+                ann.append(&mut qualifiers.into_owl(ctx));
+                self.add_genus(cls, ann);
+            }
+            obo::TermClause::UnionOf(cid) => {
+                let cls = owl::ClassExpression::from(owl::Class(cid.into_owl(ctx)));
+                ann.append(&mut qualifiers.into_owl(ctx));
+                self.add_union(cls, ann);
+            }
+            _ => unreachable!(),
         }
 
-        // Add all intersections as a single `EquivalentClasses` axiom.
-        if !intersections.is_empty() {
-            axioms.insert(owl::AnnotatedComponent::new(
+        None
+    }
+
+    /// Build the `EquivalentClasses` axioms defining the given class.
+    ///
+    /// Each axiom is returned together with the tag of the clauses it was
+    /// built from.
+    pub fn build(self, cls: &owl::Class<A>) -> FrameAxioms<A> {
+        let mut axioms = Vec::new();
+        let equivalent = |expr, ann| {
+            owl::AnnotatedComponent::new(
                 owl::Component::EquivalentClasses(owl::EquivalentClasses(vec![
-                    owl::ClassExpression::Class(owl::Class(id.clone())),
-                    owl::ClassExpression::ObjectIntersectionOf(intersections),
+                    owl::ClassExpression::Class(cls.clone()),
+                    expr,
                 ])),
-                intersections_a,
+                ann,
+            )
+        };
+
+        // Add all intersections as a single `EquivalentClasses` axiom.
+        if !self.genus.is_empty() || !self.differentia.is_empty() {
+            let mut operands = self.genus;
+            operands.extend(self.differentia);
+            axioms.push((
+                equivalent(
+                    owl::ClassExpression::ObjectIntersectionOf(operands),
+                    self.intersection_ann,
+                ),
+                Some("intersection_of"),
             ));
         }
 
         // Add all unions as a single `EquivalentClasses` axiom.
-        if !unions.is_empty() {
-            axioms.insert(owl::AnnotatedComponent::new(
-                owl::Component::EquivalentClasses(owl::EquivalentClasses(vec![
-                    owl::ClassExpression::Class(owl::Class(id.clone())),
-                    owl::ClassExpression::ObjectUnionOf(unions),
-                ])),
-                unions_a,
+        if !self.unions.is_empty() {
+            axioms.push((
+                equivalent(
+                    owl::ClassExpression::ObjectUnionOf(self.unions),
+                    self.union_ann,
+                ),
+                Some("union_of"),
             ));
         }
 
+        axioms
+    }
+}
+
+impl<A: ForIRI> Default for DefinitionBuilder<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: ForIRI> IntoOwlCtx<A> for obo::TermFrame {
+    type Owl = FrameAxioms<A>;
+    fn into_owl(mut self, ctx: &mut Context<A>) -> Self::Owl {
+        // The ID of this frame translated to an IRI.
+        let id = self.id().clone().into_inner().into_owl(ctx);
+
+        // The translated axioms, with the tag of their clause.
+        let mut axioms: Self::Owl = Vec::with_capacity(self.len() + 2);
+
+        // Prefix the label of obsolete terms if required.
+        let obsolete = is_obsolete(&self);
+        if obsolete && ctx.config.obsoletion == ObsoletionPolicy::Relabel {
+            for line in self.iter_mut() {
+                if let obo::TermClause::Name(name) = line.as_inner_mut() {
+                    if !name.as_str().starts_with("obsolete") {
+                        let label = format!("obsolete {}", name.as_str());
+                        **name = obo::UnquotedString::new(label);
+                    }
+                }
+            }
+        }
+
+        // Build the annotated class declaration.
+        let declaration = owl::AnnotatedComponent {
+            ann: BTreeSet::new(),
+            component: owl::Component::from(owl::DeclareClass(owl::Class(id.clone()))),
+        };
+        axioms.push((declaration, None));
+
+        // Add the original OBO ID as an annotation, unless the frame is
+        // anonymous and the ID is only meaningful within the document.
+        if !ctx.is_anonymous(self.id().as_inner().as_ref()) {
+            let ac = owl::AnnotatedComponent::from(owl::AnnotationAssertion {
+                subject: owl::AnnotationSubject::from(&id),
+                ann: owl::Annotation {
+                    ap: ctx.build.annotation_property(property::obo_in_owl::ID),
+                    av: owl::AnnotationValue::Literal(owl::Literal::Simple {
+                        literal: self.id().as_ref().to_string(),
+                    }),
+                },
+            });
+            axioms.push((ac, None));
+        }
+
+        // Convert remaining clauses to axioms, collecting the clauses
+        // making the logical definition of the class separately.
+        let mut definition = DefinitionBuilder::new();
+        for line in self.into_iter() {
+            if let Some(line) = definition.push(line, ctx) {
+                let tag = clause_tag(line.as_inner());
+                if let Some(ac) = line.into_owl(ctx) {
+                    axioms.push((ac, tag));
+                }
+            }
+        }
+
+        // Add the axioms of the logical definition.
+        axioms.extend(definition.build(&owl::Class(id)));
+
+        // Remove the logical axioms of obsolete terms if required.
+        if obsolete && ctx.config.obsoletion != ObsoletionPolicy::Keep {
+            axioms.retain(|(ac, _)| {
+                !matches!(
+                    ac.component,
+                    owl::Component::SubClassOf(_)
+                        | owl::Component::EquivalentClasses(_)
+                        | owl::Component::DisjointClasses(_)
+                )
+            });
+        }
+
         // Return the axioms
         axioms
     }
@@ -99,23 +351,14 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::Line<obo::TermClause> {
     fn into_owl(mut self, ctx: &mut Context<A>) -> Self::Owl {
         // Take ownership of qualifiers list.
         let qualifiers = match self.qualifiers_mut() {
-            Some(q) => std::mem::replace(q, obo::QualifierList::default()),
+            Some(q) => std::mem::take(q),
             None => obo::QualifierList::default(),
         };
 
-        match self.into_inner() {
-            //
-            obo::TermClause::IntersectionOf(Some(rid), cid) => Some(owl::AnnotatedComponent::new(
-                owl::Component::EquivalentClasses(owl::EquivalentClasses(vec![
-                    owl::ClassExpression::from(owl::Class(ctx.current_frame.clone())),
-                    owl::ClassExpression::ObjectIntersectionOf(vec![ctx.rel_class_expression(
-                        &qualifiers,
-                        *rid,
-                        *cid,
-                    )]),
-                ])),
-                qualifiers.into_owl(ctx),
-            )),
+        // Translate the line comment, if any, to an axiom annotation.
+        let mut comment = ctx.line_comment(&self);
+
+        let axiom = match self.into_inner() {
             //
             obo::TermClause::Relationship(rid, cid) => {
                 let r_iri = rid.into_owl(ctx);
@@ -138,6 +381,35 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::Line<obo::TermClause> {
                 }
             }
             //
+            obo::TermClause::PropertyValue(pv) => {
+                let mut qualifiers = qualifiers;
+                let lang = super::pv::take_language(&mut qualifiers);
+                let mut ann = pv.into_owl(ctx);
+                if let Some(lang) = lang {
+                    super::pv::set_language(&mut ann, lang);
+                }
+                let component = match ann.av {
+                    // literal values of data properties are class restrictions
+                    owl::AnnotationValue::Literal(l) if ctx.is_data_property(&ann.ap.0) => {
+                        owl::Component::from(owl::SubClassOf {
+                            sub: owl::ClassExpression::from(owl::Class(ctx.current_frame.clone())),
+                            sup: owl::ClassExpression::DataHasValue {
+                                dp: owl::DataProperty(ann.ap.0),
+                                l,
+                            },
+                        })
+                    }
+                    av => owl::Component::from(owl::AnnotationAssertion {
+                        subject: owl::AnnotationSubject::from(&ctx.current_frame),
+                        ann: owl::Annotation { ap: ann.ap, av },
+                    }),
+                };
+                Some(owl::AnnotatedComponent::new(
+                    component,
+                    qualifiers.into_owl(ctx),
+                ))
+            }
+            //
             other => {
                 if let Some(mut axiom) = other.into_owl(ctx) {
                     axiom.ann.append(&mut qualifiers.into_owl(ctx));
@@ -146,7 +418,12 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::Line<obo::TermClause> {
                     None
                 }
             }
-        }
+        };
+
+        axiom.map(|mut axiom| {
+            axiom.ann.append(&mut comment);
+            axiom
+        })
     }
 }
 
@@ -245,7 +522,6 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::TermClause {
                 }))
             }
 
-            // QUESTION: should be all grouped into a single axiom ?
             obo::TermClause::EquivalentTo(cid) => {
                 Some(owl::AnnotatedComponent::from(owl::EquivalentClasses(vec![
                     owl::ClassExpression::from(owl::Class(ctx.current_frame.clone())),
@@ -317,24 +593,6 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::TermClause {
 
             obo::TermClause::Synonym(syn) => Some(syn.into_owl(ctx)),
 
-            obo::TermClause::IntersectionOf(None, cid) => {
-                Some(owl::AnnotatedComponent::from(owl::EquivalentClasses(vec![
-                    owl::ClassExpression::from(owl::Class(ctx.current_frame.clone())),
-                    owl::ClassExpression::ObjectIntersectionOf(vec![owl::ClassExpression::from(
-                        owl::Class(cid.into_owl(ctx)),
-                    )]),
-                ])))
-            }
-
-            obo::TermClause::UnionOf(cid) => {
-                Some(owl::AnnotatedComponent::from(owl::EquivalentClasses(vec![
-                    owl::ClassExpression::from(owl::Class(ctx.current_frame.clone())),
-                    owl::ClassExpression::ObjectUnionOf(vec![owl::ClassExpression::from(
-                        owl::Class(cid.into_owl(ctx)),
-                    )]),
-                ])))
-            }
-
             // These are handled on `Line<TermClause>::into_owl`
             obo::TermClause::Relationship(rid, cid) => Some(owl::AnnotatedComponent::from(
                 owl::Component::SubClassOf(owl::SubClassOf {
@@ -343,16 +601,176 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::TermClause {
                 }),
             )),
 
-            obo::TermClause::IntersectionOf(Some(rid), cid) => Some(owl::AnnotatedComponent::from(
-                owl::Component::EquivalentClasses(owl::EquivalentClasses(vec![
-                    owl::ClassExpression::from(owl::Class(ctx.current_frame.clone())),
-                    owl::ClassExpression::ObjectIntersectionOf(vec![ctx.rel_class_expression(
-                        &obo::QualifierList::default(),
-                        *rid,
-                        *cid,
-                    )]),
-                ])),
-            )),
+            // These only make sense together with the other clauses of the
+            // frame, and are grouped by `DefinitionBuilder` in `TermFrame::into_owl`.
+            obo::TermClause::IntersectionOf(_, _) | obo::TermClause::UnionOf(_) => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn frame(text: &str) -> obo::TermFrame {
+        let doc = fastobo::from_str(format!("ontology: test\n\n{}", text)).unwrap();
+        doc.entities()[0].as_term().unwrap().clone()
+    }
+
+    #[test]
+    fn validate_single_union_of() {
+        let res = validate(&frame("[Term]\nid: TST:01\nunion_of: TST:02\n"));
+        assert!(matches!(
+            res,
+            Err(Error::InvalidFrame {
+                error: FrameError::Cardinality(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_duplicate_is_anonymous() {
+        let res = validate(&frame(
+            "[Term]\nid: TST:01\nis_anonymous: true\nis_anonymous: false\n",
+        ));
+        assert!(matches!(
+            res,
+            Err(Error::InvalidFrame {
+                error: FrameError::Cardinality(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_mixed_definitions() {
+        let res = validate(&frame(
+            "[Term]\nid: TST:01\nintersection_of: TST:02\nintersection_of: part_of TST:03\nunion_of: TST:04\nunion_of: TST:05\n",
+        ));
+        assert!(matches!(
+            res,
+            Err(Error::InvalidFrame {
+                error: FrameError::MixedDefinitions,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_invalid_cardinality() {
+        let res = validate(&frame(
+            "[Term]\nid: TST:01\nrelationship: part_of TST:02 {minCardinality=\"-1\"}\n",
+        ));
+        assert!(matches!(
+            res,
+            Err(Error::InvalidFrame {
+                error: FrameError::InvalidQualifier { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_missing_genus() {
+        let res = validate(&frame(
+            "[Term]\nid: TST:01\nintersection_of: part_of TST:02\nintersection_of: part_of TST:03\n",
+        ));
+        assert!(matches!(
+            res,
+            Err(Error::InvalidFrame {
+                error: FrameError::MissingGenus,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_genus_differentia() {
+        let res = validate(&frame(
+            "[Term]\nid: TST:01\nintersection_of: TST:02\nintersection_of: part_of TST:03\n",
+        ));
+        assert!(res.is_ok());
+    }
+
+    fn definition(text: &str) -> (FrameAxioms<String>, Vec<obo::Line<obo::TermClause>>) {
+        let doc = fastobo::from_str(format!("ontology: test\n\n{}", text)).unwrap();
+        let mut ctx = Context::<String>::from_obodoc(&doc).unwrap();
+        let frame = doc.entities()[0].as_term().unwrap().clone();
+        let id = owl::Class(frame.id().clone().into_inner().into_owl(&mut ctx));
+        let mut builder = DefinitionBuilder::new();
+        let rest = frame
+            .into_iter()
+            .filter_map(|line| builder.push(line, &mut ctx))
+            .collect();
+        (builder.build(&id), rest)
+    }
+
+    #[test]
+    fn definition_genus_first() {
+        let (axioms, rest) = definition(
+            "[Term]\nid: TST:01\nintersection_of: part_of TST:03\nintersection_of: TST:02\n",
+        );
+        assert_eq!(axioms.len(), 1);
+        assert!(rest.is_empty());
+        match &axioms[0].0.component {
+            owl::Component::EquivalentClasses(owl::EquivalentClasses(exprs)) => match &exprs[1] {
+                owl::ClassExpression::ObjectIntersectionOf(operands) => {
+                    assert!(matches!(operands[0], owl::ClassExpression::Class(_)));
+                    assert!(matches!(
+                        operands[1],
+                        owl::ClassExpression::ObjectSomeValuesFrom { .. }
+                    ));
+                }
+                other => panic!("unexpected class expression: {:?}", other),
+            },
+            other => panic!("unexpected component: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn definition_equivalent_to_not_grouped() {
+        let (axioms, rest) = definition(
+            "[Term]\nid: TST:01\nequivalent_to: TST:02\nequivalent_to: TST:03\nunion_of: TST:04\nunion_of: TST:05\n",
+        );
+        assert_eq!(axioms.len(), 1);
+        assert_eq!(rest.len(), 2);
+        assert!(matches!(
+            &axioms[0].0.component,
+            owl::Component::EquivalentClasses(owl::EquivalentClasses(exprs))
+                if matches!(&exprs[1], owl::ClassExpression::ObjectUnionOf(u) if u.len() == 2)
+        ));
+    }
+
+    #[test]
+    fn clause_union_of_alone() {
+        let doc = fastobo::from_str("ontology: test\n").unwrap();
+        let mut ctx = Context::<String>::from_obodoc(&doc).unwrap();
+        let id = obo::ClassIdent::from(obo::PrefixedIdent::new("TST", "02"));
+        let clause = obo::TermClause::UnionOf(Box::new(id));
+        assert!(clause.into_owl(&mut ctx).is_none());
+    }
+
+    #[test]
+    fn check_replacements_missing() {
+        let doc = fastobo::from_str(
+            "ontology: test\n\n[Term]\nid: TST:01\nis_obsolete: true\nreplaced_by: TST:02\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            check_replacements(&doc),
+            Err(Error::InvalidFrame {
+                error: FrameError::MissingReplacement { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn check_replacements_not_obsolete() {
+        let doc =
+            fastobo::from_str("ontology: test\n\n[Term]\nid: TST:01\nconsider: TST:02\n").unwrap();
+        assert!(check_replacements(&doc).is_ok());
+    }
+}
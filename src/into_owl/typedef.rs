@@ -2,13 +2,45 @@ use std::collections::BTreeSet;
 use std::iter::FromIterator;
 
 use fastobo::ast as obo;
+use fastobo::semantics::OboFrame;
 use horned_owl::model as owl;
 use horned_owl::model::ForIRI;
 
+use super::clause_tag;
 use super::Context;
+use super::FrameAxioms;
 use super::IntoOwlCtx;
 use crate::constants::datatype;
 use crate::constants::property;
+use crate::error::Error;
+use crate::error::FrameError;
+
+/// Check that a typedef frame can be translated according to the OBO 1.4 semantics.
+///
+/// This checks the cardinality of all clauses, and that the relation is not
+/// defined both as a union and as an intersection.
+pub fn validate(frame: &obo::TypedefFrame) -> Result<(), Error> {
+    let error = |error: FrameError| Error::InvalidFrame {
+        id: frame.id().as_inner().as_ref().clone(),
+        error,
+    };
+
+    frame
+        .cardinality_check()
+        .map_err(|e| error(FrameError::from(e)))?;
+
+    let has_intersection = frame
+        .iter()
+        .any(|line| matches!(line.as_inner(), obo::TypedefClause::IntersectionOf(_)));
+    let has_union = frame
+        .iter()
+        .any(|line| matches!(line.as_inner(), obo::TypedefClause::UnionOf(_)));
+    if has_intersection && has_union {
+        return Err(error(FrameError::MixedDefinitions));
+    }
+
+    Ok(())
+}
 
 fn is_annotation_property(frame: &obo::TypedefFrame) -> bool {
     frame
@@ -17,46 +49,94 @@ fn is_annotation_property(frame: &obo::TypedefFrame) -> bool {
 }
 
 impl<A: ForIRI> IntoOwlCtx<A> for obo::TypedefFrame {
-    type Owl = BTreeSet<owl::AnnotatedComponent<A>>;
+    type Owl = FrameAxioms<A>;
     fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
         // The ID of this frame translated to an IRI.
         let id = self.id().clone().into_inner().into_owl(ctx);
 
-        // The translated axioms.
-        let mut axioms: Self::Owl = BTreeSet::new();
+        // The translated axioms, with the tag of their clause.
+        let mut axioms: Self::Owl = Vec::with_capacity(self.len() + 2);
 
         // Check if we translate as object or annotation property.
         if is_annotation_property(&self) {
             // Annotation property.
-            axioms.insert(owl::AnnotatedComponent {
-                ann: BTreeSet::new(),
-                component: owl::Component::<A>::from(owl::DeclareAnnotationProperty(
-                    id.clone().into(),
-                )),
-            });
+            axioms.push((
+                owl::AnnotatedComponent {
+                    ann: BTreeSet::new(),
+                    component: owl::Component::<A>::from(owl::DeclareAnnotationProperty(
+                        id.clone().into(),
+                    )),
+                },
+                None,
+            ));
             ctx.in_annotation = true;
+            ctx.in_data_property = false;
+        } else if ctx.is_data_property(&id) {
+            // Data property.
+            axioms.push((
+                owl::AnnotatedComponent {
+                    ann: BTreeSet::new(),
+                    component: owl::Component::<A>::from(owl::DeclareDataProperty(
+                        id.clone().into(),
+                    )),
+                },
+                None,
+            ));
+            ctx.in_annotation = false;
+            ctx.in_data_property = true;
         } else {
             // Object property.
-            axioms.insert(owl::AnnotatedComponent {
-                ann: BTreeSet::new(),
-                component: owl::Component::<A>::from(owl::DeclareObjectProperty(id.clone().into())),
-            });
+            axioms.push((
+                owl::AnnotatedComponent {
+                    ann: BTreeSet::new(),
+                    component: owl::Component::<A>::from(owl::DeclareObjectProperty(
+                        id.clone().into(),
+                    )),
+                },
+                None,
+            ));
             ctx.in_annotation = false;
+            ctx.in_data_property = false;
         }
 
-        // Add the original OBO ID as an annotation.
-        axioms.insert(owl::AnnotatedComponent::from(owl::AnnotationAssertion {
-            subject: owl::AnnotationSubject::from(&id),
-            ann: owl::Annotation {
-                ap: ctx.build.annotation_property(property::obo_in_owl::ID),
-                av: owl::AnnotationValue::Literal(owl::Literal::Simple {
-                    literal: self.id().as_ref().to_string(),
+        // Add the original OBO ID as an annotation, unless the frame is
+        // anonymous and the ID is only meaningful within the document.
+        if !ctx.is_anonymous(self.id().as_inner().as_ref()) {
+            axioms.push((
+                owl::AnnotatedComponent::from(owl::AnnotationAssertion {
+                    subject: owl::AnnotationSubject::from(&id),
+                    ann: owl::Annotation {
+                        ap: ctx.build.annotation_property(property::obo_in_owl::ID),
+                        av: owl::AnnotationValue::Literal(owl::Literal::Simple {
+                            literal: self.id().as_ref().to_string(),
+                        }),
+                    },
                 }),
-            },
-        }));
+                None,
+            ));
+        }
 
         // Add the typedef clauses.
-        axioms.extend(self.into_iter().flat_map(|line| line.into_owl(ctx)));
+        for line in self.into_iter() {
+            let tag = clause_tag(line.as_inner());
+            let equivalent = matches!(line.as_inner(), obo::TypedefClause::EquivalentTo(_));
+            if let Some(ac) = line.into_owl(ctx) {
+                // Metadata tags are equivalent through mutual subproperty axioms.
+                if let (true, owl::Component::SubAnnotationPropertyOf(sub)) =
+                    (equivalent, &ac.component)
+                {
+                    let reverse = owl::AnnotatedComponent {
+                        component: owl::Component::from(owl::SubAnnotationPropertyOf {
+                            sup: sub.sub.clone(),
+                            sub: sub.sup.clone(),
+                        }),
+                        ann: ac.ann.clone(),
+                    };
+                    axioms.push((reverse, tag));
+                }
+                axioms.push((ac, tag));
+            }
+        }
 
         // Return the axioms
         axioms
@@ -65,8 +145,17 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::TypedefFrame {
 
 impl<A: ForIRI> IntoOwlCtx<A> for obo::Line<obo::TypedefClause> {
     type Owl = Option<owl::AnnotatedComponent<A>>;
-    fn into_owl(self, ctx: &mut Context<A>) -> Self::Owl {
-        self.into_inner().into_owl(ctx)
+    fn into_owl(mut self, ctx: &mut Context<A>) -> Self::Owl {
+        // Extract the language tag of property values, if any.
+        let lang = self.qualifiers_mut().and_then(super::pv::take_language);
+        let mut comment = ctx.line_comment(&self);
+        let mut axiom = self.into_inner().into_owl(ctx)?;
+        axiom.ann.append(&mut comment);
+        if let (Some(lang), owl::Component::AnnotationAssertion(aa)) = (lang, &mut axiom.component)
+        {
+            super::pv::set_language(&mut aa.ann, lang);
+        }
+        Some(axiom)
     }
 }
 
@@ -161,6 +250,65 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::TypedefClause {
                 }))
             }
 
+            // Data property axioms, only for typedefs with a datatype range.
+            obo::TypedefClause::Domain(cid) if ctx.in_data_property => {
+                Some(owl::AnnotatedComponent::from(owl::DataPropertyDomain {
+                    dp: owl::DataProperty::from(&ctx.current_frame),
+                    ce: owl::ClassExpression::Class(owl::Class::from(cid.into_owl(ctx))),
+                }))
+            }
+
+            obo::TypedefClause::Range(cid) if ctx.in_data_property => {
+                Some(owl::AnnotatedComponent::from(owl::DataPropertyRange {
+                    dp: owl::DataProperty::from(&ctx.current_frame),
+                    dr: owl::DataRange::Datatype(owl::Datatype::from(cid.into_owl(ctx))),
+                }))
+            }
+
+            obo::TypedefClause::IsA(rid) if ctx.in_data_property => {
+                Some(owl::AnnotatedComponent::from(owl::SubDataPropertyOf {
+                    sup: owl::DataProperty::from(rid.into_owl(ctx)),
+                    sub: owl::DataProperty::from(&ctx.current_frame),
+                }))
+            }
+
+            obo::TypedefClause::EquivalentTo(rid) if ctx.in_data_property => Some(
+                owl::AnnotatedComponent::from(owl::EquivalentDataProperties(vec![
+                    owl::DataProperty::from(&ctx.current_frame),
+                    owl::DataProperty::from(rid.into_owl(ctx)),
+                ])),
+            ),
+
+            obo::TypedefClause::DisjointFrom(rid) if ctx.in_data_property => Some(
+                owl::AnnotatedComponent::from(owl::DisjointDataProperties(vec![
+                    owl::DataProperty::from(&ctx.current_frame),
+                    owl::DataProperty::from(rid.into_owl(ctx)),
+                ])),
+            ),
+
+            obo::TypedefClause::IsFunctional(true) if ctx.in_data_property => {
+                Some(owl::AnnotatedComponent::from(owl::FunctionalDataProperty(
+                    owl::DataProperty::from(&ctx.current_frame),
+                )))
+            }
+
+            // Object property characteristics have no data property equivalent.
+            obo::TypedefClause::HoldsOverChain(_, _)
+            | obo::TypedefClause::IsReflexive(_)
+            | obo::TypedefClause::IsSymmetric(_)
+            | obo::TypedefClause::IsAsymmetric(_)
+            | obo::TypedefClause::IsTransitive(_)
+            | obo::TypedefClause::IsInverseFunctional(_)
+            | obo::TypedefClause::IntersectionOf(_)
+            | obo::TypedefClause::UnionOf(_)
+            | obo::TypedefClause::InverseOf(_)
+            | obo::TypedefClause::TransitiveOver(_)
+            | obo::TypedefClause::EquivalentToChain(_, _)
+                if ctx.in_data_property =>
+            {
+                None
+            }
+
             obo::TypedefClause::Domain(cid) => {
                 Some(owl::AnnotatedComponent::from(owl::ObjectPropertyDomain {
                     ope: owl::ObjectPropertyExpression::from(&ctx.current_frame),
@@ -312,12 +460,14 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::TypedefClause {
             }
 
             obo::TypedefClause::EquivalentTo(cls) => {
+                // NB: OWL 2 has no axiom for equivalent annotation properties,
+                //     so the reverse subproperty axiom is added by the frame.
                 if ctx.in_annotation {
                     Some(owl::AnnotatedComponent::from(
-                        owl::EquivalentDataProperties(vec![
-                            ctx.current_frame.clone().into(),
-                            cls.into_owl(ctx).into(),
-                        ]),
+                        owl::SubAnnotationPropertyOf {
+                            sup: cls.into_owl(ctx).into(),
+                            sub: ctx.current_frame.clone().into(),
+                        },
                     ))
                 } else {
                     Some(owl::AnnotatedComponent::from(
@@ -491,3 +641,40 @@ impl<A: ForIRI> IntoOwlCtx<A> for obo::TypedefClause {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn frame(text: &str) -> obo::TypedefFrame {
+        let doc = fastobo::from_str(format!("ontology: test\n\n{}", text)).unwrap();
+        doc.entities()[0].as_typedef().unwrap().clone()
+    }
+
+    #[test]
+    fn validate_single_union_of() {
+        let res = validate(&frame("[Typedef]\nid: rel\nunion_of: other\n"));
+        assert!(matches!(
+            res,
+            Err(Error::InvalidFrame {
+                error: FrameError::Cardinality(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_mixed_definitions() {
+        let res = validate(&frame(
+            "[Typedef]\nid: rel\nintersection_of: a\nintersection_of: b\nunion_of: c\nunion_of: d\n",
+        ));
+        assert!(matches!(
+            res,
+            Err(Error::InvalidFrame {
+                error: FrameError::MixedDefinitions,
+                ..
+            })
+        ));
+    }
+}
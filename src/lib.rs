@@ -5,27 +5,52 @@
 extern crate lazy_static;
 #[macro_use]
 extern crate thiserror;
+#[cfg(feature = "obographs")]
+#[macro_use]
+extern crate serde;
 
 extern crate curie;
 extern crate fastobo;
 extern crate horned_owl;
 
+mod config;
 pub mod constants;
 mod error;
 mod into_owl;
+#[cfg(feature = "obographs")]
+pub mod obographs;
 
+pub use config::Config;
+pub use config::DatePolicy;
+pub use config::ObsoletionPolicy;
+pub use config::Profile;
+pub use config::ProfilePolicy;
+pub use config::RolePolicy;
+pub use config::SynonymTypePolicy;
 pub use error::Error;
+pub use error::FrameError;
+pub use error::ProfileViolation;
 pub use error::Result;
+pub use error::Role;
+pub use error::RoleConflict;
 pub use into_owl::IntoOwl;
 pub use into_owl::IntoOwlPrefixes;
 
+// NB: not part of the public API, only exposed for the benchmarks.
+#[doc(hidden)]
+pub use into_owl::Context;
+#[doc(hidden)]
+pub use into_owl::IntoOwlCtx;
+
 // ---------------------------------------------------------------------------
 
 /// Create a [`curie::PrefixMapping`] instance with default prefixes declared.
 ///
 /// The OBO Format 1.4 reference states that any OBO document translated into
 /// OWL has the following prefixes declared implicitly: `xsd`, `owl`,
-/// `oboInOwl`, `xml`, `rdf`, `dc` and `rdfs`.
+/// `oboInOwl`, `xml`, `rdf`, `dc` and `rdfs`. The `dcterms` and `foaf`
+/// prefixes, used by the OBO Foundry ontology metadata conventions, are
+/// declared as well.
 ///
 /// [`curie::PrefixMapping`]: https://docs.rs/curie/0.0.8/curie/struct.PrefixMapping.html
 pub fn obo_prefixes() -> curie::PrefixMapping {
@@ -39,6 +64,10 @@ pub fn obo_prefixes() -> curie::PrefixMapping {
     prefixes.add_prefix("xml", constants::uri::XML).unwrap();
     prefixes.add_prefix("rdf", constants::uri::RDF).unwrap();
     prefixes.add_prefix("dc", constants::uri::DC).unwrap();
+    prefixes
+        .add_prefix("dcterms", constants::uri::DCTERMS)
+        .unwrap();
+    prefixes.add_prefix("foaf", constants::uri::FOAF).unwrap();
     prefixes.add_prefix("rdfs", constants::uri::RDFS).unwrap();
     prefixes
 }
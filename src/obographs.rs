@@ -0,0 +1,365 @@
+//! OBO Graphs representation of the translated OWL axioms.
+//!
+//! This module converts the axioms produced by [`IntoOwl`](../trait.IntoOwl.html)
+//! into an [OBO Graphs](https://github.com/geneontology/obographs) document,
+//! which can then be serialized to JSON with any `serde` serializer. Only the
+//! constructs emitted by this crate are supported: classes, properties and
+//! individuals become nodes, `SubClassOf` axioms between classes or to an
+//! existential restriction become edges, and `EquivalentClasses` axioms
+//! become equivalent node sets or logical definitions.
+
+use std::collections::BTreeMap;
+
+use horned_owl::model as owl;
+use horned_owl::model::ForIRI;
+
+use crate::constants::property;
+
+/// A collection of OBO graphs, with the prefixes used to shorten their IRIs.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphDocument {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prefixes: BTreeMap<String, String>,
+    pub graphs: Vec<Graph>,
+}
+
+/// A graph corresponding to a single ontology.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Graph {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equivalent_nodes_sets: Vec<EquivalentNodesSet>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logical_definition_axioms: Vec<LogicalDefinitionAxiom>,
+}
+
+/// The type of an entity described by a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum NodeType {
+    Class,
+    Property,
+    Individual,
+}
+
+/// A node of the graph, describing a single entity.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lbl: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub ty: Option<NodeType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+}
+
+/// An edge of the graph, relating two nodes with a predicate.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Edge {
+    pub sub: String,
+    pub pred: String,
+    pub obj: String,
+}
+
+/// The metadata attached to a node or a graph.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<DefinitionPropertyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subsets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub synonyms: Vec<SynonymPropertyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub xrefs: Vec<XrefPropertyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub basic_property_values: Vec<BasicPropertyValue>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+}
+
+/// A textual definition with its supporting cross-references.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DefinitionPropertyValue {
+    pub val: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub xrefs: Vec<String>,
+}
+
+/// A synonym with its scope predicate, type and cross-references.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SynonymPropertyValue {
+    pub pred: String,
+    pub val: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synonym_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub xrefs: Vec<String>,
+}
+
+/// A database cross-reference.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct XrefPropertyValue {
+    pub val: String,
+}
+
+/// Any other annotation, with the full IRI of its property as predicate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BasicPropertyValue {
+    pub pred: String,
+    pub val: String,
+}
+
+/// A set of nodes declared equivalent to each other.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquivalentNodesSet {
+    pub node_ids: Vec<String>,
+}
+
+/// A genus-differentia definition of a class.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicalDefinitionAxiom {
+    pub defined_class_id: String,
+    pub genus_ids: Vec<String>,
+    pub restrictions: Vec<ExistentialRestriction>,
+}
+
+/// An existential restriction used in a logical definition.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExistentialRestriction {
+    pub property_id: String,
+    pub filler_id: String,
+}
+
+// ---------------------------------------------------------------------------
+
+/// Build an OBO Graphs document from the axioms of an OWL ontology.
+///
+/// The IRIs of the OWL entities are used as node identifiers, and the
+/// `prefixes` (usually obtained with [`IntoOwlPrefixes`]) are recorded in
+/// the document so that consumers can shorten them back into CURIEs.
+///
+/// [`IntoOwlPrefixes`]: ../trait.IntoOwlPrefixes.html
+pub fn from_owl<'a, A, I>(components: I, prefixes: &curie::PrefixMapping) -> GraphDocument
+where
+    A: ForIRI + 'a,
+    I: IntoIterator<Item = &'a owl::AnnotatedComponent<A>>,
+{
+    let mut graph = Graph::default();
+    let mut nodes: BTreeMap<String, Node> = BTreeMap::new();
+    let mut edges = Vec::new();
+
+    for ac in components {
+        match &ac.component {
+            owl::Component::OntologyID(id) => {
+                graph.id = id.iri.as_ref().map(|iri| iri.to_string());
+            }
+            owl::Component::OntologyAnnotation(owl::OntologyAnnotation(ann)) => {
+                let meta = graph.meta.get_or_insert_with(Meta::default);
+                meta.basic_property_values.push(BasicPropertyValue {
+                    pred: ann.ap.0.to_string(),
+                    val: annotation_value(&ann.av),
+                });
+            }
+            owl::Component::DeclareClass(owl::DeclareClass(cls)) => {
+                node(&mut nodes, &cls.0).ty = Some(NodeType::Class);
+            }
+            owl::Component::DeclareObjectProperty(owl::DeclareObjectProperty(op)) => {
+                node(&mut nodes, &op.0).ty = Some(NodeType::Property);
+            }
+            owl::Component::DeclareAnnotationProperty(owl::DeclareAnnotationProperty(ap)) => {
+                node(&mut nodes, &ap.0).ty = Some(NodeType::Property);
+            }
+            owl::Component::DeclareNamedIndividual(owl::DeclareNamedIndividual(ni)) => {
+                node(&mut nodes, &ni.0).ty = Some(NodeType::Individual);
+            }
+            owl::Component::AnnotationAssertion(aa) => {
+                if let owl::AnnotationSubject::IRI(iri) = &aa.subject {
+                    annotate(node(&mut nodes, iri), &aa.ann, &ac.ann);
+                }
+            }
+            owl::Component::SubClassOf(sco) => {
+                if let owl::ClassExpression::Class(sub) = &sco.sub {
+                    match &sco.sup {
+                        owl::ClassExpression::Class(sup) => edges.push(Edge {
+                            sub: sub.0.to_string(),
+                            pred: String::from("is_a"),
+                            obj: sup.0.to_string(),
+                        }),
+                        owl::ClassExpression::ObjectSomeValuesFrom {
+                            ope: owl::ObjectPropertyExpression::ObjectProperty(op),
+                            bce,
+                        } => {
+                            if let owl::ClassExpression::Class(filler) = bce.as_ref() {
+                                edges.push(Edge {
+                                    sub: sub.0.to_string(),
+                                    pred: op.0.to_string(),
+                                    obj: filler.0.to_string(),
+                                });
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            owl::Component::EquivalentClasses(owl::EquivalentClasses(ces)) => {
+                equivalence(&mut graph, ces);
+            }
+            _ => (),
+        }
+    }
+
+    edges.sort();
+    graph.nodes = nodes.into_values().collect();
+    graph.edges = edges;
+
+    GraphDocument {
+        prefixes: prefixes
+            .mappings()
+            .map(|(prefix, url)| (prefix.clone(), url.clone()))
+            .collect(),
+        graphs: vec![graph],
+    }
+}
+
+/// Get the node with the given IRI, creating it if needed.
+fn node<'a, A: ForIRI>(nodes: &'a mut BTreeMap<String, Node>, iri: &owl::IRI<A>) -> &'a mut Node {
+    nodes.entry(iri.to_string()).or_insert_with(|| Node {
+        id: iri.to_string(),
+        lbl: None,
+        ty: None,
+        meta: None,
+    })
+}
+
+/// Get the lexical form of an annotation value.
+fn annotation_value<A: ForIRI>(av: &owl::AnnotationValue<A>) -> String {
+    match av {
+        owl::AnnotationValue::Literal(owl::Literal::Simple { literal })
+        | owl::AnnotationValue::Literal(owl::Literal::Language { literal, .. })
+        | owl::AnnotationValue::Literal(owl::Literal::Datatype { literal, .. }) => literal.clone(),
+        owl::AnnotationValue::IRI(iri) => iri.to_string(),
+        owl::AnnotationValue::AnonymousIndividual(ai) => ai.to_string(),
+    }
+}
+
+/// Get the values of the `oboInOwl:hasDbXref` axiom annotations.
+fn xrefs<A: ForIRI>(anns: &std::collections::BTreeSet<owl::Annotation<A>>) -> Vec<String> {
+    anns.iter()
+        .filter(|ann| ann.ap.0.as_ref() == property::obo_in_owl::HAS_DBXREF)
+        .map(|ann| annotation_value(&ann.av))
+        .collect()
+}
+
+/// Record an annotation assertion in the metadata of a node.
+fn annotate<A: ForIRI>(
+    node: &mut Node,
+    ann: &owl::Annotation<A>,
+    axiom_anns: &std::collections::BTreeSet<owl::Annotation<A>>,
+) {
+    let val = annotation_value(&ann.av);
+    let ap = ann.ap.0.as_ref();
+    if ap == property::rdfs::LABEL {
+        node.lbl = Some(val);
+        return;
+    }
+
+    let meta = node.meta.get_or_insert_with(Meta::default);
+    match ap {
+        property::iao::DEFINITION => {
+            meta.definition = Some(DefinitionPropertyValue {
+                val,
+                xrefs: xrefs(axiom_anns),
+            });
+        }
+        property::rdfs::COMMENT => meta.comments.push(val),
+        property::obo_in_owl::IN_SUBSET => meta.subsets.push(val),
+        property::obo_in_owl::HAS_DBXREF => meta.xrefs.push(XrefPropertyValue { val }),
+        property::owl::DEPRECATED => meta.deprecated = val == "true",
+        property::obo_in_owl::HAS_EXACT_SYNONYM
+        | property::obo_in_owl::HAS_BROAD_SYNONYM
+        | property::obo_in_owl::HAS_NARROW_SYNONYM
+        | property::obo_in_owl::HAS_RELATED_SYNONYM => {
+            meta.synonyms.push(SynonymPropertyValue {
+                pred: ap
+                    .trim_start_matches(crate::constants::uri::OBO_IN_OWL)
+                    .to_string(),
+                val,
+                synonym_type: axiom_anns
+                    .iter()
+                    .find(|a| a.ap.0.as_ref() == property::obo_in_owl::HAS_SYNONYM_TYPE)
+                    .map(|a| annotation_value(&a.av)),
+                xrefs: xrefs(axiom_anns),
+            });
+        }
+        _ => meta.basic_property_values.push(BasicPropertyValue {
+            pred: ap.to_string(),
+            val,
+        }),
+    }
+}
+
+/// Record an `EquivalentClasses` axiom in the graph.
+fn equivalence<A: ForIRI>(graph: &mut Graph, ces: &[owl::ClassExpression<A>]) {
+    // Equivalence between named classes.
+    let named: Vec<String> = ces
+        .iter()
+        .filter_map(|ce| match ce {
+            owl::ClassExpression::Class(cls) => Some(cls.0.to_string()),
+            _ => None,
+        })
+        .collect();
+    if named.len() == ces.len() {
+        graph
+            .equivalent_nodes_sets
+            .push(EquivalentNodesSet { node_ids: named });
+        return;
+    }
+
+    // Genus-differentia definition of a named class.
+    if let [owl::ClassExpression::Class(defined), owl::ClassExpression::ObjectIntersectionOf(ops)] =
+        ces
+    {
+        let mut genus_ids = Vec::new();
+        let mut restrictions = Vec::new();
+        for op in ops {
+            match op {
+                owl::ClassExpression::Class(genus) => genus_ids.push(genus.0.to_string()),
+                owl::ClassExpression::ObjectSomeValuesFrom {
+                    ope: owl::ObjectPropertyExpression::ObjectProperty(property),
+                    bce,
+                } => match bce.as_ref() {
+                    owl::ClassExpression::Class(filler) => {
+                        restrictions.push(ExistentialRestriction {
+                            property_id: property.0.to_string(),
+                            filler_id: filler.0.to_string(),
+                        })
+                    }
+                    _ => return,
+                },
+                _ => return,
+            }
+        }
+        graph
+            .logical_definition_axioms
+            .push(LogicalDefinitionAxiom {
+                defined_class_id: defined.0.to_string(),
+                genus_ids,
+                restrictions,
+            });
+    }
+}
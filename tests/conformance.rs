@@ -0,0 +1,212 @@
+//! Conformance tests for the translation of every OBO 1.4 clause.
+//!
+//! Each fixture in `tests/data/conformance` exercises a group of clauses
+//! following the examples of the [OBO 1.4 semantics], and the `coverage`
+//! test makes sure every clause of the `fastobo` syntax tree appears in at
+//! least one of the fixtures.
+//!
+//! [OBO 1.4 semantics]: https://owlcollab.github.io/oboformat/doc/obo-syntax.html
+
+extern crate fastobo;
+extern crate fastobo_owl;
+extern crate pretty_assertions;
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use fastobo::ast as obo;
+use fastobo::semantics::OboClause;
+use fastobo_owl::IntoOwl;
+use horned_owl::model::Build;
+use horned_owl::ontology::set::SetOntology;
+use pretty_assertions::assert_eq;
+
+/// The tags of all header clauses, with `unreserved` for unreserved clauses.
+const HEADER_TAGS: &[&str] = &[
+    "format-version",
+    "data-version",
+    "date",
+    "saved-by",
+    "auto-generated-by",
+    "import",
+    "subsetdef",
+    "synonymtypedef",
+    "default-namespace",
+    "namespace-id-rule",
+    "idspace",
+    "treat-xrefs-as-equivalent",
+    "treat-xrefs-as-genus-differentia",
+    "treat-xrefs-as-reverse-genus-differentia",
+    "treat-xrefs-as-relationship",
+    "treat-xrefs-as-is_a",
+    "treat-xrefs-as-has-subclass",
+    "property_value",
+    "remark",
+    "ontology",
+    "owl-axioms",
+    "unreserved",
+];
+
+/// The tags of all term frame clauses.
+const TERM_TAGS: &[&str] = &[
+    "is_anonymous",
+    "name",
+    "namespace",
+    "alt_id",
+    "def",
+    "comment",
+    "subset",
+    "synonym",
+    "xref",
+    "builtin",
+    "property_value",
+    "is_a",
+    "intersection_of",
+    "union_of",
+    "equivalent_to",
+    "disjoint_from",
+    "relationship",
+    "created_by",
+    "creation_date",
+    "is_obsolete",
+    "replaced_by",
+    "consider",
+];
+
+/// The tags of all typedef frame clauses.
+const TYPEDEF_TAGS: &[&str] = &[
+    "is_anonymous",
+    "name",
+    "namespace",
+    "alt_id",
+    "def",
+    "comment",
+    "subset",
+    "synonym",
+    "xref",
+    "property_value",
+    "domain",
+    "range",
+    "builtin",
+    "holds_over_chain",
+    "is_anti_symmetric",
+    "is_cyclic",
+    "is_reflexive",
+    "is_symmetric",
+    "is_asymmetric",
+    "is_transitive",
+    "is_functional",
+    "is_inverse_functional",
+    "is_a",
+    "intersection_of",
+    "union_of",
+    "equivalent_to",
+    "disjoint_from",
+    "inverse_of",
+    "transitive_over",
+    "equivalent_to_chain",
+    "disjoint_over",
+    "relationship",
+    "is_obsolete",
+    "replaced_by",
+    "consider",
+    "created_by",
+    "creation_date",
+    "expand_assertion_to",
+    "expand_expression_to",
+    "is_metadata_tag",
+    "is_class_level",
+];
+
+fn data_dir() -> PathBuf {
+    let mut p = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    p.push("tests");
+    p.push("data");
+    p.push("conformance");
+    p
+}
+
+macro_rules! conformancetest {
+    ($name:ident) => {
+        #[test]
+        fn $name() {
+            let dir = data_dir();
+            let input_path = dir.join(format!("{}.input.obo", stringify!($name)));
+            let output_path = dir.join(format!("{}.output.owl", stringify!($name)));
+
+            // Parse the OBO doc and convert it to OWL.
+            let obo_doc = fastobo::from_file(&input_path).expect("could not parse input file");
+            let actual = obo_doc
+                .into_owl::<SetOntology<String>>()
+                .expect("could not convert ontology to OWL");
+
+            // Read the expected OWL
+            let mut reader = std::fs::File::open(&output_path)
+                .map(std::io::BufReader::new)
+                .expect("could not open output file");
+            let (expected, _prefixes): (SetOntology<String>, _) =
+                horned_owl::io::owx::reader::read_with_build(&mut reader, &Build::new())
+                    .expect("could not parse output file");
+
+            // reorder
+            let mut exp: Vec<_> = expected.iter().collect();
+            exp.sort();
+            let mut act: Vec<_> = actual.iter().collect();
+            act.sort();
+
+            assert_eq!(act, exp);
+        }
+    };
+}
+
+conformancetest!(header);
+conformancetest!(header_owl_axioms);
+conformancetest!(header_treat_xrefs);
+conformancetest!(relationship_qualifiers);
+conformancetest!(term_annotations);
+conformancetest!(term_logical);
+conformancetest!(typedef_annotations);
+conformancetest!(typedef_logical);
+conformancetest!(typedef_metadata_tag);
+
+#[test]
+fn coverage() {
+    let mut header = BTreeSet::new();
+    let mut term = BTreeSet::new();
+    let mut typedef = BTreeSet::new();
+
+    for entry in std::fs::read_dir(data_dir()).expect("could not read fixtures directory") {
+        let path = entry.unwrap().path();
+        if !path.to_string_lossy().ends_with(".input.obo") {
+            continue;
+        }
+        let doc = fastobo::from_file(&path).expect("could not parse input file");
+        for clause in doc.header().iter() {
+            header.insert(match clause {
+                obo::HeaderClause::Unreserved(_, _) => String::from("unreserved"),
+                other => other.tag().to_string(),
+            });
+        }
+        for entity in doc.entities() {
+            match entity {
+                obo::EntityFrame::Term(frame) => {
+                    term.extend(frame.iter().map(|l| l.as_inner().tag().to_string()));
+                }
+                obo::EntityFrame::Typedef(frame) => {
+                    typedef.extend(frame.iter().map(|l| l.as_inner().tag().to_string()));
+                }
+                obo::EntityFrame::Instance(_) => (),
+            }
+        }
+    }
+
+    let missing = |tags: &[&'static str], found: &BTreeSet<String>| {
+        tags.iter()
+            .filter(|tag| !found.contains(**tag))
+            .copied()
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(missing(HEADER_TAGS, &header), Vec::<&str>::new());
+    assert_eq!(missing(TERM_TAGS, &term), Vec::<&str>::new());
+    assert_eq!(missing(TYPEDEF_TAGS, &typedef), Vec::<&str>::new());
+}
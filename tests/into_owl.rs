@@ -5,13 +5,19 @@ extern crate pretty_assertions;
 
 use std::path::PathBuf;
 
+use fastobo_owl::Config;
 use fastobo_owl::IntoOwl;
+use fastobo_owl::ObsoletionPolicy;
+use fastobo_owl::RolePolicy;
 use horned_owl::model::Build;
 use horned_owl::ontology::set::SetOntology;
 use pretty_assertions::assert_eq;
 
 macro_rules! converttest {
     ($name:ident) => {
+        converttest!($name, Config::default());
+    };
+    ($name:ident, $config:expr) => {
         #[test]
         fn $name() {
             let dir = {
@@ -28,14 +34,14 @@ macro_rules! converttest {
             // Parse the OBO doc and convert it to OWL.
             let obo_doc = fastobo::from_file(&input_path).expect("could not parse input file");
             let actual = obo_doc
-                .into_owl::<SetOntology<String>>()
+                .into_owl_with_config::<SetOntology<String>>($config)
                 .expect("could not convert ontology to OWL");
 
             // Read the expected OWL
             let mut reader = std::fs::File::open(&output_path)
                 .map(std::io::BufReader::new)
                 .expect("could not open output file");
-            let (expected, _prefixes) =
+            let (expected, _prefixes): (SetOntology<String>, _) =
                 horned_owl::io::owx::reader::read_with_build(&mut reader, &Build::new())
                     .expect("could not parse output file");
 
@@ -55,13 +61,66 @@ macro_rules! converttest {
 converttest!(def_xref);
 converttest!(equivalent_to);
 converttest!(header);
+converttest!(header_metadata);
 converttest!(intersection_of);
 converttest!(is_a);
+converttest!(is_anonymous);
+converttest!(
+    line_comments,
+    Config {
+        line_comments: true,
+        ..Config::default()
+    }
+);
 converttest!(name);
+converttest!(
+    no_declarations,
+    Config {
+        declare_entities: false,
+        ..Config::default()
+    }
+);
+converttest!(
+    obsolete,
+    Config {
+        obsoletion: ObsoletionPolicy::Relabel,
+        ..Config::default()
+    }
+);
 converttest!(property_value);
+converttest!(property_value_datatype);
+converttest!(
+    provenance,
+    Config {
+        provenance: Some(String::from("http://example.com/provenance")),
+        ..Config::default()
+    }
+);
 converttest!(qualifier);
 converttest!(subsetdef);
+converttest!(
+    role_conflicts,
+    Config {
+        role_conflicts: RolePolicy::Drop,
+        ..Config::default()
+    }
+);
+converttest!(
+    role_conflicts_undeclared,
+    Config {
+        role_conflicts: RolePolicy::Drop,
+        ..Config::default()
+    }
+);
 converttest!(synonym);
 converttest!(union_of);
 converttest!(typedef_shorthand);
 converttest!(version_iri);
+
+converttest!(
+    data_property,
+    Config {
+        data_properties: true,
+        ..Config::default()
+    }
+);
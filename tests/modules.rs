@@ -0,0 +1,203 @@
+extern crate fastobo;
+extern crate fastobo_owl;
+extern crate horned_owl;
+
+use std::path::PathBuf;
+
+use fastobo::ast::SubsetIdent;
+use fastobo::ast::UnprefixedIdent;
+use fastobo_owl::Config;
+use fastobo_owl::IntoOwl;
+use horned_owl::model::AnnotationAssertion;
+use horned_owl::model::AnnotationSubject;
+use horned_owl::model::ClassExpression;
+use horned_owl::model::Component;
+use horned_owl::model::ForIRI;
+use horned_owl::model::SubClassOf;
+use horned_owl::model::IRI;
+use horned_owl::ontology::set::SetOntology;
+
+const RDFS_LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+
+fn data(name: &str) -> PathBuf {
+    let mut p = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    p.push("tests");
+    p.push("data");
+    p.push("modules");
+    p.push(name);
+    p
+}
+
+fn declared_classes<A: ForIRI>(ont: &SetOntology<A>) -> Vec<String> {
+    let mut classes: Vec<String> = ont
+        .iter()
+        .filter_map(|ac| match &ac.component {
+            Component::DeclareClass(dc) => Some(dc.0 .0.to_string()),
+            _ => None,
+        })
+        .collect();
+    classes.sort();
+    classes
+}
+
+fn ontology_id<A: ForIRI>(ont: &SetOntology<A>) -> (Option<IRI<A>>, Option<IRI<A>>) {
+    ont.iter()
+        .find_map(|ac| match &ac.component {
+            Component::OntologyID(id) => Some((id.iri.clone(), id.viri.clone())),
+            _ => None,
+        })
+        .expect("missing ontology ID")
+}
+
+#[test]
+fn split_by_namespace() {
+    let doc = fastobo::from_file(data("namespaces.obo")).expect("could not parse input file");
+    let modules = doc
+        .into_owl_by_namespace::<SetOntology<String>>()
+        .expect("could not convert ontology to OWL");
+
+    let names: Vec<String> = modules.keys().map(|ns| ns.to_string()).collect();
+    assert_eq!(names, vec!["biological_process", "molecular_function"]);
+
+    let bp = modules.values().next().unwrap();
+    assert_eq!(
+        declared_classes(bp),
+        vec![
+            "http://purl.obolibrary.org/obo/TST_001",
+            "http://purl.obolibrary.org/obo/TST_003",
+        ]
+    );
+    let (iri, viri) = ontology_id(bp);
+    assert_eq!(
+        iri.unwrap().as_ref(),
+        "http://purl.obolibrary.org/obo/tst/biological_process.owl"
+    );
+    assert_eq!(
+        viri.unwrap().as_ref(),
+        "http://purl.obolibrary.org/obo/tst/2025-01-01/tst/biological_process.owl"
+    );
+
+    let mf = modules.values().nth(1).unwrap();
+    assert_eq!(
+        declared_classes(mf),
+        vec!["http://purl.obolibrary.org/obo/TST_002"]
+    );
+
+    // typedefs are shared by all modules
+    for ont in modules.values() {
+        assert!(ont.iter().any(|ac| matches!(
+            &ac.component,
+            Component::DeclareObjectProperty(op) if op.0.as_ref() == "http://purl.obolibrary.org/obo/tst#part_of"
+        )));
+    }
+}
+
+#[test]
+fn split_by_namespace_with_config() {
+    let doc = fastobo::from_file(data("namespaces.obo")).expect("could not parse input file");
+    let config = Config {
+        base_iri: Some(String::from("https://example.com/")),
+        ..Config::default()
+    };
+    let modules = doc
+        .into_owl_by_namespace_with_config::<SetOntology<String>>(config)
+        .expect("could not convert ontology to OWL");
+
+    let bp = modules.values().next().unwrap();
+    let (iri, _) = ontology_id(bp);
+    assert_eq!(
+        iri.unwrap().as_ref(),
+        "https://example.com/tst/biological_process.owl"
+    );
+}
+
+fn subclass_edges<A: ForIRI>(ont: &SetOntology<A>) -> Vec<(String, String)> {
+    let mut edges: Vec<(String, String)> = ont
+        .iter()
+        .filter_map(|ac| match &ac.component {
+            Component::SubClassOf(SubClassOf {
+                sub: ClassExpression::Class(sub),
+                sup: ClassExpression::Class(sup),
+            }) => Some((sub.0.to_string(), sup.0.to_string())),
+            _ => None,
+        })
+        .collect();
+    edges.sort();
+    edges
+}
+
+fn labelled<A: ForIRI>(ont: &SetOntology<A>, iri: &str) -> bool {
+    ont.iter().any(|ac| match &ac.component {
+        Component::AnnotationAssertion(AnnotationAssertion {
+            subject: AnnotationSubject::IRI(subject),
+            ann,
+        }) => subject.as_ref() == iri && ann.ap.0.as_ref() == RDFS_LABEL,
+        _ => false,
+    })
+}
+
+#[test]
+fn subset() {
+    let doc = fastobo::from_file(data("subset.obo")).expect("could not parse input file");
+    let subset = SubsetIdent::from(UnprefixedIdent::new("slim"));
+    let ont = doc
+        .into_owl_subset::<SetOntology<String>>(&[subset], false)
+        .expect("could not convert ontology to OWL");
+
+    assert_eq!(
+        declared_classes(&ont),
+        vec![
+            "http://purl.obolibrary.org/obo/TST_001",
+            "http://purl.obolibrary.org/obo/TST_003",
+        ]
+    );
+    assert!(subclass_edges(&ont).is_empty());
+    assert!(ont.iter().any(|ac| matches!(
+        &ac.component,
+        Component::DeclareObjectProperty(op) if op.0.as_ref() == "http://purl.obolibrary.org/obo/tst#part_of"
+    )));
+    assert!(!ont.iter().any(|ac| matches!(
+        &ac.component,
+        Component::DeclareObjectProperty(op) if op.0.as_ref() == "http://purl.obolibrary.org/obo/tst#has_part"
+    )));
+
+    // typedefs used by kept typedefs or in property values are kept
+    assert!(labelled(
+        &ont,
+        "http://purl.obolibrary.org/obo/tst#overlaps"
+    ));
+    assert!(labelled(&ont, "http://purl.obolibrary.org/obo/tst#curator"));
+}
+
+#[test]
+fn subset_closure() {
+    let doc = fastobo::from_file(data("subset.obo")).expect("could not parse input file");
+    let subset = SubsetIdent::from(UnprefixedIdent::new("slim"));
+    let ont = doc
+        .into_owl_subset::<SetOntology<String>>(&[subset], true)
+        .expect("could not convert ontology to OWL");
+
+    assert_eq!(
+        subclass_edges(&ont),
+        vec![(
+            String::from("http://purl.obolibrary.org/obo/TST_003"),
+            String::from("http://purl.obolibrary.org/obo/TST_001"),
+        )]
+    );
+}
+
+#[test]
+fn subset_with_config() {
+    let doc = fastobo::from_file(data("subset.obo")).expect("could not parse input file");
+    let subset = SubsetIdent::from(UnprefixedIdent::new("slim"));
+    let config = Config {
+        base_iri: Some(String::from("https://example.com/")),
+        ..Config::default()
+    };
+    let ont = doc
+        .into_owl_subset_with_config::<SetOntology<String>>(&[subset], false, config)
+        .expect("could not convert ontology to OWL");
+
+    let (iri, _) = ontology_id(&ont);
+    assert_eq!(iri.unwrap().as_ref(), "https://example.com/tst.owl");
+}
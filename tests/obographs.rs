@@ -0,0 +1,103 @@
+#![cfg(feature = "obographs")]
+
+extern crate fastobo;
+extern crate fastobo_owl;
+extern crate horned_owl;
+extern crate serde_json;
+
+use std::path::PathBuf;
+
+use fastobo_owl::obographs;
+use fastobo_owl::IntoOwl;
+use fastobo_owl::IntoOwlPrefixes;
+use horned_owl::ontology::set::SetOntology;
+
+#[test]
+fn from_owl() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests");
+    path.push("data");
+    path.push("modules");
+    path.push("obographs.obo");
+
+    let doc = fastobo::from_file(&path).expect("could not parse input file");
+    let prefixes = doc.prefixes();
+    let ont = doc
+        .into_owl::<SetOntology<String>>()
+        .expect("could not convert ontology to OWL");
+    let gd = obographs::from_owl(ont.iter(), &prefixes);
+
+    assert_eq!(
+        gd.prefixes.get("oboInOwl").map(String::as_str),
+        Some("http://www.geneontology.org/formats/oboInOwl#")
+    );
+    assert_eq!(gd.graphs.len(), 1);
+    let graph = &gd.graphs[0];
+    assert_eq!(
+        graph.id.as_deref(),
+        Some("http://purl.obolibrary.org/obo/tst.owl")
+    );
+
+    let root = graph
+        .nodes
+        .iter()
+        .find(|n| n.id == "http://purl.obolibrary.org/obo/TST_001")
+        .unwrap();
+    assert_eq!(root.lbl.as_deref(), Some("root"));
+    assert_eq!(root.ty, Some(obographs::NodeType::Class));
+    let meta = root.meta.as_ref().unwrap();
+    let def = meta.definition.as_ref().unwrap();
+    assert_eq!(def.val, "The root of the ontology.");
+    assert_eq!(def.xrefs, vec!["PMID:1234"]);
+    assert_eq!(meta.xrefs[0].val, "Wikipedia:Root");
+
+    let part = graph
+        .nodes
+        .iter()
+        .find(|n| n.id == "http://purl.obolibrary.org/obo/TST_002")
+        .unwrap();
+    let syn = &part.meta.as_ref().unwrap().synonyms[0];
+    assert_eq!(syn.pred, "hasExactSynonym");
+    assert_eq!(syn.val, "piece");
+    assert_eq!(
+        syn.synonym_type.as_deref(),
+        Some("http://purl.obolibrary.org/obo/tst#systematic_synonym")
+    );
+    assert_eq!(syn.xrefs, vec!["ISBN:1234"]);
+
+    assert_eq!(
+        graph.edges,
+        vec![
+            obographs::Edge {
+                sub: String::from("http://purl.obolibrary.org/obo/TST_002"),
+                pred: String::from("http://purl.obolibrary.org/obo/tst#part_of"),
+                obj: String::from("http://purl.obolibrary.org/obo/TST_001"),
+            },
+            obographs::Edge {
+                sub: String::from("http://purl.obolibrary.org/obo/TST_002"),
+                pred: String::from("is_a"),
+                obj: String::from("http://purl.obolibrary.org/obo/TST_001"),
+            },
+        ]
+    );
+
+    assert_eq!(graph.logical_definition_axioms.len(), 1);
+    let ldef = &graph.logical_definition_axioms[0];
+    assert_eq!(
+        ldef.defined_class_id,
+        "http://purl.obolibrary.org/obo/TST_003"
+    );
+    assert_eq!(
+        ldef.genus_ids,
+        vec!["http://purl.obolibrary.org/obo/TST_002"]
+    );
+    assert_eq!(
+        ldef.restrictions[0].property_id,
+        "http://purl.obolibrary.org/obo/tst#part_of"
+    );
+
+    // check the document can be serialized and read back
+    let json = serde_json::to_string(&gd).unwrap();
+    let gd2: obographs::GraphDocument = serde_json::from_str(&json).unwrap();
+    assert_eq!(gd, gd2);
+}
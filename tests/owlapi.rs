@@ -0,0 +1,240 @@
+//! Differential tests against the OBO translation of the OWL API.
+//!
+//! Each `{name}.obo` document in `tests/data/owlapi` is checked in together
+//! with a `{name}.owx` reference ontology produced offline by the OWL API,
+//! for instance with [ROBOT](http://robot.obolibrary.org/):
+//!
+//! ```console
+//! $ robot convert --input tests/data/owlapi/{name}.obo --output tests/data/owlapi/{name}.owx
+//! ```
+//!
+//! The document is translated with `fastobo-owl` and both ontologies are
+//! compared axiom by axiom. The differences are grouped by axiom kind, and
+//! the ones not listed in [`ALLOWED`] make the tests fail, as do entries of
+//! [`ALLOWED`] which no longer match any difference.
+
+extern crate curie;
+extern crate fastobo;
+extern crate fastobo_owl;
+extern crate horned_owl;
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use fastobo_owl::IntoOwl;
+use fastobo_owl::IntoOwlPrefixes;
+use horned_owl::io::ofn::writer::AsFunctional;
+use horned_owl::model::AnnotatedComponent;
+use horned_owl::model::Build;
+use horned_owl::model::Kinded;
+use horned_owl::ontology::set::SetOntology;
+
+/// The known divergences with the OWL API, with the reason why.
+///
+/// Each entry gives the side of the difference (`missing` for axioms only
+/// produced by the OWL API, `extra` for axioms only produced by
+/// `fastobo-owl`), and a prefix of the axiom rendered in functional syntax
+/// using the OBO prefixes, so that each entry only allows one kind of axiom.
+const ALLOWED: &[(&str, &str, &str)] = &[
+    (
+        "missing",
+        "Declaration(AnnotationProperty(oboInOwl:default-namespace))",
+        "the OWL API uses a generic property for `default-namespace`",
+    ),
+    (
+        "missing",
+        "Annotation(oboInOwl:default-namespace ",
+        "the OWL API uses a generic property for `default-namespace`",
+    ),
+    (
+        "extra",
+        "Declaration(AnnotationProperty(oboInOwl:hasDefaultNamespace))",
+        "the OWL API uses a generic property for `default-namespace`",
+    ),
+    (
+        "extra",
+        "Annotation(oboInOwl:hasDefaultNamespace ",
+        "the OWL API uses a generic property for `default-namespace`",
+    ),
+    (
+        "extra",
+        "Annotation(owl:versionInfo ",
+        "the OWL API only uses `data-version` to build the version IRI",
+    ),
+    (
+        "missing",
+        "Declaration(AnnotationProperty(oboInOwl:shorthand))",
+        "typedef shorthands are only used to build the IRI of the relation",
+    ),
+    (
+        "missing",
+        "AnnotationAssertion(oboInOwl:shorthand obo:BFO_0000050 ",
+        "typedef shorthands are only used to build the IRI of the relation",
+    ),
+    (
+        "extra",
+        "Declaration(ObjectProperty(obo:tst#part_of))",
+        "typedefs with a shorthand keep their local IRI, declared equivalent to the xref",
+    ),
+    (
+        "extra",
+        "EquivalentObjectProperties(obo:tst#part_of obo:BFO_0000050)",
+        "typedefs with a shorthand keep their local IRI, declared equivalent to the xref",
+    ),
+    (
+        "missing",
+        "AnnotationAssertion(oboInOwl:id obo:BFO_0000050 ",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "missing",
+        "AnnotationAssertion(oboInOwl:hasOBONamespace obo:BFO_0000050 ",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "missing",
+        "AnnotationAssertion(rdfs:label obo:BFO_0000050 ",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "missing",
+        "AnnotationAssertion(oboInOwl:hasDbXref obo:BFO_0000050 ",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "missing",
+        "TransitiveObjectProperty(obo:BFO_0000050)",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "extra",
+        "AnnotationAssertion(oboInOwl:id obo:tst#part_of ",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "extra",
+        "AnnotationAssertion(oboInOwl:hasOBONamespace obo:tst#part_of ",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "extra",
+        "AnnotationAssertion(rdfs:label obo:tst#part_of ",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "extra",
+        "AnnotationAssertion(oboInOwl:hasDbXref obo:tst#part_of ",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "extra",
+        "TransitiveObjectProperty(obo:tst#part_of)",
+        "the clauses of typedefs with a shorthand are translated on their local IRI",
+    ),
+    (
+        "missing",
+        "AnnotationAssertion(rdfs:label oboInOwl:",
+        "the OWL API labels the `oboInOwl` properties it uses",
+    ),
+    (
+        "missing",
+        "AnnotationAssertion(rdfs:label obo:IAO_0000115 ",
+        "the OWL API labels the `IAO` properties it uses",
+    ),
+];
+
+/// The differences between the reference and the translated ontologies.
+///
+/// Differences are keyed by side and axiom kind, and rendered in functional
+/// syntax so that they can be matched against [`ALLOWED`].
+type Differences = BTreeMap<(&'static str, String), Vec<String>>;
+
+/// Translate a document and compare it with its reference ontology.
+fn differences(path: &Path) -> Differences {
+    let doc = fastobo::from_file(path).expect("could not parse input file");
+    let prefixes = doc.prefixes();
+    let actual = doc
+        .into_owl::<SetOntology<String>>()
+        .expect("could not convert ontology to OWL");
+
+    let mut reader = std::fs::File::open(path.with_extension("owx"))
+        .map(std::io::BufReader::new)
+        .expect("could not open reference file");
+    let (expected, _): (SetOntology<String>, _) =
+        horned_owl::io::owx::reader::read_with_build(&mut reader, &Build::new())
+            .expect("could not parse reference file");
+
+    let expected = expected.iter().collect::<BTreeSet<_>>();
+    let actual = actual.iter().collect::<BTreeSet<_>>();
+
+    let mut differences = Differences::new();
+    let mut record = |side: &'static str, ac: &AnnotatedComponent<String>| {
+        let kind = format!("{:?}", ac.kind());
+        let text = ac.as_functional_with_prefixes(&prefixes).to_string();
+        differences.entry((side, kind)).or_default().push(text);
+    };
+    for ac in expected.difference(&actual) {
+        record("missing", ac);
+    }
+    for ac in actual.difference(&expected) {
+        record("extra", ac);
+    }
+    differences
+}
+
+#[test]
+fn reference() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/owlapi");
+    let mut paths = std::fs::read_dir(&dir)
+        .expect("could not read reference directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map(|ext| ext == "obo").unwrap_or(false))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut message = String::new();
+    let mut used = BTreeSet::new();
+    for path in paths.iter() {
+        let mut header = false;
+        for ((side, kind), axioms) in differences(path) {
+            let unexpected = axioms
+                .iter()
+                .filter(|axiom| {
+                    let allowed = ALLOWED
+                        .iter()
+                        .position(|(s, pattern, _)| *s == side && axiom.starts_with(pattern));
+                    used.extend(allowed);
+                    allowed.is_none()
+                })
+                .collect::<Vec<_>>();
+            if !unexpected.is_empty() {
+                if !header {
+                    writeln!(message, "unexpected differences for {}:", path.display()).unwrap();
+                    header = true;
+                }
+                writeln!(message, "- {} {} ({})", side, kind, unexpected.len()).unwrap();
+                for axiom in unexpected {
+                    writeln!(message, "    {}", axiom).unwrap();
+                }
+            }
+        }
+    }
+
+    for (i, (side, pattern, reason)) in ALLOWED.iter().enumerate() {
+        if !used.contains(&i) {
+            writeln!(
+                message,
+                "allowed difference never found: {} {:?} ({})",
+                side, pattern, reason
+            )
+            .unwrap();
+        }
+    }
+
+    if !message.is_empty() {
+        panic!("{}", message);
+    }
+}
@@ -0,0 +1,919 @@
+//! Round-trip tests translating OBO documents to OWL and back to OBO.
+//!
+//! The OWL ontologies produced by `fastobo-owl` are translated back to OBO
+//! with a minimal reverse translator, which only knows about the axioms the
+//! forward translation can produce. Both documents are then normalised to
+//! sets of clauses per frame, ignoring qualifiers and line comments, and the
+//! clauses missing from the round-tripped document are reported as losses.
+//!
+//! Some clauses cannot be recovered from the OWL ontology by design: these
+//! are listed in [`LOSSY`] together with the reason they are lost, and any
+//! other loss makes the tests fail. Clauses which are translated faithfully
+//! but cannot be written back identically are listed in [`REWRITTEN`].
+
+extern crate curie;
+extern crate fastobo;
+extern crate fastobo_owl;
+extern crate horned_owl;
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use fastobo::ast as obo;
+use fastobo::semantics::OboClause;
+use fastobo_owl::constants::property;
+use fastobo_owl::constants::uri;
+use fastobo_owl::IntoOwl;
+use fastobo_owl::IntoOwlPrefixes;
+use horned_owl::model as owl;
+use horned_owl::ontology::set::SetOntology;
+
+/// The clauses intentionally lost by the translation, with the reason why.
+const LOSSY: &[(&str, &str)] = &[
+    (
+        "Header/idspace",
+        "ID spaces are only used to expand identifiers",
+    ),
+    (
+        "Header/treat-xrefs-as-equivalent",
+        "header macros are expanded",
+    ),
+    (
+        "Header/treat-xrefs-as-genus-differentia",
+        "header macros are expanded",
+    ),
+    (
+        "Header/treat-xrefs-as-reverse-genus-differentia",
+        "header macros are expanded",
+    ),
+    (
+        "Header/treat-xrefs-as-relationship",
+        "header macros are expanded",
+    ),
+    ("Header/treat-xrefs-as-is_a", "header macros are expanded"),
+    (
+        "Header/treat-xrefs-as-has-subclass",
+        "header macros are expanded",
+    ),
+    (
+        "Header/owl-axioms",
+        "OWL axioms are merged with the translated axioms",
+    ),
+    (
+        "Header/unreserved",
+        "unreserved header clauses have no OWL counterpart",
+    ),
+    ("Term/builtin", "built-in frames have no OWL counterpart"),
+    ("Typedef/builtin", "built-in frames have no OWL counterpart"),
+    (
+        "Typedef/disjoint_over",
+        "T(disjoint_over) is undefined in OBO 1.4",
+    ),
+    (
+        "Typedef/equivalent_to_chain",
+        "chains are translated as sub-property chains",
+    ),
+    (
+        "Typedef/intersection_of",
+        "relation intersections become sub-properties",
+    ),
+    (
+        "Typedef/union_of",
+        "relation unions become super-properties",
+    ),
+    (
+        "Typedef/is_metadata_tag",
+        "metadata tags used in logical axioms are also declared as object properties",
+    ),
+];
+
+/// The clauses which may be rewritten by the translation, with the reason why.
+///
+/// A clause with the first tag lost by the round-trip is only considered
+/// rewritten if a clause with the second tag was added to the same frame.
+const REWRITTEN: &[(&str, &str, &str)] = &[
+    (
+        "Header/date",
+        "Header/date",
+        "dates become `xsd:dateTime` literals",
+    ),
+    (
+        "Header/property_value",
+        "Header/property_value",
+        "literals with an XSD datatype are canonicalised",
+    ),
+    (
+        "Term/property_value",
+        "Term/property_value",
+        "literals with an XSD datatype are canonicalised",
+    ),
+    (
+        "Typedef/property_value",
+        "Typedef/property_value",
+        "literals with an XSD datatype are canonicalised",
+    ),
+    (
+        "Term/property_value",
+        "Term/created_by",
+        "`dc:creator` property values can not be told from `created_by` clauses",
+    ),
+];
+
+// --- Normalisation ---------------------------------------------------------
+
+/// A normalised document, as the set of tagged clauses of each frame.
+type Clauses = BTreeMap<String, BTreeSet<(String, String)>>;
+
+/// Normalise a document to the set of tagged clauses of each frame.
+///
+/// Header macros are expanded and default namespaces assigned beforehand,
+/// like the forward translation does. Instance frames are ignored since they
+/// are not translated. Clause tags are prefixed with the kind of their frame,
+/// and xrefs are sorted since they are translated to a set of annotations.
+fn normalise(mut doc: obo::OboDoc) -> Clauses {
+    doc.assign_namespaces().ok();
+    doc.treat_xrefs();
+
+    let mut clauses = Clauses::new();
+    let header = clauses.entry(String::from("[Header]")).or_default();
+    for clause in doc.header().iter() {
+        let tag = match clause {
+            obo::HeaderClause::Unreserved(_, _) => "unreserved",
+            other => other.tag(),
+        };
+        header.insert((format!("Header/{}", tag), clause.to_string()));
+    }
+    for entity in doc.entities() {
+        let (key, frame) = match entity {
+            obo::EntityFrame::Term(frame) => (
+                format!("[Term] {}", frame.id().as_inner()),
+                frame
+                    .iter()
+                    .map(|line| {
+                        let mut clause = line.as_inner().clone();
+                        match &mut clause {
+                            obo::TermClause::Def(def) => def.xrefs_mut().sort(),
+                            obo::TermClause::Synonym(syn) => syn.xrefs_mut().sort(),
+                            _ => (),
+                        }
+                        (format!("Term/{}", clause.tag()), clause.to_string())
+                    })
+                    .collect::<BTreeSet<_>>(),
+            ),
+            obo::EntityFrame::Typedef(frame) => (
+                format!("[Typedef] {}", frame.id().as_inner()),
+                frame
+                    .iter()
+                    .map(|line| {
+                        let mut clause = line.as_inner().clone();
+                        match &mut clause {
+                            obo::TypedefClause::Def(def) => def.xrefs_mut().sort(),
+                            obo::TypedefClause::Synonym(syn) => syn.xrefs_mut().sort(),
+                            _ => (),
+                        }
+                        (format!("Typedef/{}", clause.tag()), clause.to_string())
+                    })
+                    .collect::<BTreeSet<_>>(),
+            ),
+            obo::EntityFrame::Instance(_) => continue,
+        };
+        clauses.entry(key).or_default().extend(frame);
+    }
+    clauses
+}
+
+/// The clauses lost, added or rewritten by a round-trip, grouped by tag.
+#[derive(Debug, Default)]
+struct Report {
+    lost: BTreeMap<String, Vec<String>>,
+    added: BTreeMap<String, Vec<String>>,
+    rewritten: BTreeMap<String, Vec<String>>,
+}
+
+impl Report {
+    fn new(expected: &Clauses, actual: &Clauses) -> Self {
+        let empty = BTreeSet::new();
+        let mut report = Report::default();
+        let frames = expected
+            .keys()
+            .chain(actual.keys())
+            .collect::<BTreeSet<_>>();
+        for frame in frames {
+            let before = expected.get(frame).unwrap_or(&empty);
+            let after = actual.get(frame).unwrap_or(&empty);
+            let mut lost = before.difference(after).collect::<Vec<_>>();
+            let mut added = after.difference(before).collect::<Vec<_>>();
+
+            // Pair the lost and added clauses which are only rewritten.
+            for (from, to, _) in REWRITTEN.iter() {
+                while let (Some(i), Some(j)) = (
+                    lost.iter().position(|(tag, _)| tag == from),
+                    added.iter().position(|(tag, _)| tag == to),
+                ) {
+                    let (_, old) = lost.remove(i);
+                    let (_, new) = added.remove(j);
+                    let rewritten = report.rewritten.entry(from.to_string()).or_default();
+                    rewritten.push(format!("{} {} => {}", frame, old, new));
+                }
+            }
+
+            for (tag, clause) in lost {
+                let lost = report.lost.entry(tag.clone()).or_default();
+                lost.push(format!("{} {}", frame, clause));
+            }
+            for (tag, clause) in added {
+                let added = report.added.entry(tag.clone()).or_default();
+                added.push(format!("{} {}", frame, clause));
+            }
+        }
+        report
+    }
+
+    /// Get the differences not expected from the translation.
+    fn unexpected(&self) -> Vec<(&str, &String, &Vec<String>)> {
+        let lost = self
+            .lost
+            .iter()
+            .filter(|(tag, _)| !LOSSY.iter().any(|(lossy, _)| lossy == tag))
+            .map(|(tag, clauses)| ("lost", tag, clauses));
+        let added = self
+            .added
+            .iter()
+            .map(|(tag, clauses)| ("added", tag, clauses));
+        lost.chain(added).collect()
+    }
+}
+
+// --- Reverse translation ---------------------------------------------------
+
+/// A minimal translator from the produced OWL ontologies back to OBO.
+struct Reverse<'a> {
+    ontology: &'a SetOntology<String>,
+    prefixes: curie::PrefixMapping,
+    ontology_iri: String,
+    version_iri: Option<String>,
+    ids: HashMap<String, String>,
+    anonymous: HashSet<String>,
+}
+
+impl<'a> Reverse<'a> {
+    fn new(ontology: &'a SetOntology<String>, prefixes: curie::PrefixMapping) -> Self {
+        let mut ids = HashMap::new();
+        let mut anonymous = HashSet::new();
+        let mut ontology_iri = String::new();
+        let mut version_iri = None;
+        for ac in ontology.iter() {
+            match &ac.component {
+                owl::Component::OntologyID(id) => {
+                    if let Some(iri) = &id.iri {
+                        ontology_iri = iri.to_string();
+                    }
+                    version_iri = id.viri.as_ref().map(|viri| viri.to_string());
+                }
+                // NB: anonymous frames are skolemised without an `oboInOwl:id`.
+                owl::Component::DeclareClass(owl::DeclareClass(owl::Class(iri))) => {
+                    if let Some((_, local)) = iri.as_ref().split_once("/.well-known/genid/") {
+                        ids.insert(iri.to_string(), local.replacen('_', ":", 1));
+                        anonymous.insert(iri.to_string());
+                    }
+                }
+                owl::Component::AnnotationAssertion(aa)
+                    if aa.ann.ap.0.as_ref() == property::obo_in_owl::ID =>
+                {
+                    if let (owl::AnnotationSubject::IRI(iri), Some(id)) =
+                        (&aa.subject, literal(&aa.ann.av))
+                    {
+                        ids.insert(iri.to_string(), id.to_string());
+                    }
+                }
+                _ => (),
+            }
+        }
+        Self {
+            ontology,
+            prefixes,
+            ontology_iri,
+            version_iri,
+            ids,
+            anonymous,
+        }
+    }
+
+    /// Get the OBO identifier of an IRI.
+    fn ident(&self, iri: &str) -> String {
+        if let Some(id) = self.ids.get(iri) {
+            return id.clone();
+        }
+        let base = self
+            .ontology_iri
+            .strip_suffix(".owl")
+            .unwrap_or(&self.ontology_iri);
+        if let Some(local) = iri
+            .strip_prefix(&self.ontology_iri)
+            .or_else(|| iri.strip_prefix(base))
+            .and_then(|rest| rest.strip_prefix('#'))
+        {
+            return obo::UnprefixedIdent::new(local).to_string();
+        }
+        if let Some((prefix, local)) = iri.strip_prefix(uri::OBO).and_then(|s| s.split_once('_')) {
+            return format!("{}:{}", prefix, local);
+        }
+        match self.prefixes.shrink_iri(iri) {
+            Ok(curie) => curie.to_string(),
+            Err(_) => iri.to_string(),
+        }
+    }
+
+    /// Get the OBO identifier of the ontology from its IRI.
+    fn ontology(&self) -> String {
+        let iri = self
+            .ontology_iri
+            .strip_suffix(".owl")
+            .unwrap_or(&self.ontology_iri);
+        iri.strip_prefix(uri::OBO).unwrap_or(iri).to_string()
+    }
+
+    /// Get the data version of the ontology from its version IRI.
+    fn data_version(&self) -> Option<&str> {
+        let path = self.version_iri.as_ref()?.strip_prefix(uri::OBO)?;
+        let (name, rest) = path.split_once('/')?;
+        let (version, file) = rest.rsplit_once('/')?;
+        file.strip_suffix(".owl")
+            .filter(|stem| *stem == name)
+            .map(|_| version)
+    }
+
+    /// Get the xrefs recorded in the annotations of an axiom.
+    fn xrefs(&self, ann: &BTreeSet<owl::Annotation<String>>) -> String {
+        let xrefs = ann
+            .iter()
+            .filter(|a| a.ap.0.as_ref() == property::obo_in_owl::HAS_DBXREF)
+            .filter_map(|a| literal(&a.av))
+            .collect::<Vec<_>>();
+        format!("[{}]", xrefs.join(", "))
+    }
+
+    /// Translate an annotation to an OBO clause, for frames and the header.
+    fn annotation(
+        &self,
+        ann: &owl::Annotation<String>,
+        axiom: &BTreeSet<owl::Annotation<String>>,
+    ) -> Option<String> {
+        let ap = ann.ap.0.as_ref();
+        let text = literal(&ann.av);
+        let quoted = || obo::QuotedString::new(text.unwrap_or_default()).to_string();
+        let unquoted = || obo::UnquotedString::new(text.unwrap_or_default()).to_string();
+        if let Some(scope) = scope(ap) {
+            let ty = axiom
+                .iter()
+                .find(|a| a.ap.0.as_ref() == property::obo_in_owl::HAS_SYNONYM_TYPE)
+                .and_then(|a| iri(&a.av))
+                .map(|ty| format!(" {}", self.ident(ty)))
+                .unwrap_or_default();
+            let xrefs = self.xrefs(axiom);
+            return Some(format!("synonym: {} {}{} {}", quoted(), scope, ty, xrefs));
+        }
+        let clause = match ap {
+            property::obo_in_owl::ID => return None,
+            property::rdfs::LABEL => format!("name: {}", unquoted()),
+            property::rdfs::COMMENT => format!("comment: {}", unquoted()),
+            property::obo_in_owl::HAS_OBO_NAMESPACE => format!("namespace: {}", text?),
+            property::obo_in_owl::HAS_ALTERNATIVE_ID => format!("alt_id: {}", text?),
+            property::obo_in_owl::IN_SUBSET => format!("subset: {}", self.ident(iri(&ann.av)?)),
+            property::obo_in_owl::CONSIDER => format!("consider: {}", self.ident(iri(&ann.av)?)),
+            property::obo_in_owl::IS_CYCLIC => format!("is_cyclic: {}", text?),
+            property::iao::REPLACED_BY => format!("replaced_by: {}", self.ident(iri(&ann.av)?)),
+            property::iao::IS_ANTI_SYMETRIC => format!("is_anti_symmetric: {}", text?),
+            property::iao::DEFINITION => format!("def: {} {}", quoted(), self.xrefs(axiom)),
+            property::owl::DEPRECATED => format!("is_obsolete: {}", text?),
+            property::dc::CREATOR => format!("created_by: {}", unquoted()),
+            property::dc::DATE => format!("creation_date: {}", text?),
+            property::obo_in_owl::HAS_DBXREF => {
+                let desc = axiom
+                    .iter()
+                    .find(|a| a.ap.0.as_ref() == property::rdfs::LABEL)
+                    .and_then(|a| literal(&a.av))
+                    .map(|d| format!(" {}", obo::QuotedString::new(d)))
+                    .unwrap_or_default();
+                format!("xref: {}{}", text?, desc)
+            }
+            _ => {
+                let value = match &ann.av {
+                    owl::AnnotationValue::IRI(iri) => self.ident(iri.as_ref()),
+                    owl::AnnotationValue::Literal(owl::Literal::Datatype {
+                        literal,
+                        datatype_iri,
+                    }) => format!(
+                        "{} {}",
+                        obo::QuotedString::new(literal.as_str()),
+                        self.ident(datatype_iri.as_ref())
+                    ),
+                    owl::AnnotationValue::Literal(owl::Literal::Simple { literal }) => {
+                        format!("{} xsd:string", obo::QuotedString::new(literal.as_str()))
+                    }
+                    owl::AnnotationValue::Literal(owl::Literal::Language { literal, lang }) => {
+                        let literal = format!("{}@{}", literal, lang);
+                        format!("{} rdf:langString", obo::QuotedString::new(literal))
+                    }
+                    _ => return None,
+                };
+                format!("property_value: {} {}", self.ident(ap), value)
+            }
+        };
+        Some(clause)
+    }
+
+    /// Translate a class expression used in a logical definition.
+    fn differentia(&self, ce: &owl::ClassExpression<String>) -> Option<String> {
+        match ce {
+            owl::ClassExpression::Class(c) => Some(self.ident(c.0.as_ref())),
+            // NB: qualifiers are ignored by the normalisation.
+            owl::ClassExpression::ObjectSomeValuesFrom {
+                ope: owl::ObjectPropertyExpression::ObjectProperty(op),
+                bce,
+            }
+            | owl::ClassExpression::ObjectAllValuesFrom {
+                ope: owl::ObjectPropertyExpression::ObjectProperty(op),
+                bce,
+            }
+            | owl::ClassExpression::ObjectExactCardinality {
+                ope: owl::ObjectPropertyExpression::ObjectProperty(op),
+                bce,
+                ..
+            }
+            | owl::ClassExpression::ObjectMinCardinality {
+                ope: owl::ObjectPropertyExpression::ObjectProperty(op),
+                bce,
+                ..
+            }
+            | owl::ClassExpression::ObjectMaxCardinality {
+                ope: owl::ObjectPropertyExpression::ObjectProperty(op),
+                bce,
+                ..
+            } => {
+                // NB: `cardinality=0` is translated to a complement restriction.
+                let cls = match bce.as_ref() {
+                    owl::ClassExpression::ObjectComplementOf(ce) => ce.as_ref(),
+                    ce => ce,
+                };
+                match cls {
+                    owl::ClassExpression::Class(c) => Some(format!(
+                        "{} {}",
+                        self.ident(op.0.as_ref()),
+                        self.ident(c.0.as_ref())
+                    )),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Translate a logical axiom to the clauses of the frame it belongs to.
+    fn logical(&self, component: &owl::Component<String>) -> Vec<(String, String)> {
+        use horned_owl::model::ClassExpression::Class;
+        use horned_owl::model::ObjectPropertyExpression::ObjectProperty;
+        use horned_owl::model::SubObjectPropertyExpression as Sub;
+
+        let mut clauses = Vec::new();
+        match component {
+            owl::Component::SubClassOf(ax) => {
+                if let Class(sub) = &ax.sub {
+                    let subject = sub.0.to_string();
+                    match &ax.sup {
+                        Class(sup) => {
+                            clauses.push((subject, format!("is_a: {}", self.ident(sup.0.as_ref()))))
+                        }
+                        sup => {
+                            if let Some(rel) = self.differentia(sup) {
+                                clauses.push((subject, format!("relationship: {}", rel)));
+                            }
+                        }
+                    }
+                }
+            }
+            owl::Component::EquivalentClasses(ax) => {
+                if let (Some(Class(cls)), Some(expr)) = (ax.0.first(), ax.0.get(1)) {
+                    let subject = cls.0.to_string();
+                    match expr {
+                        Class(other) => clauses.push((
+                            subject,
+                            format!("equivalent_to: {}", self.ident(other.0.as_ref())),
+                        )),
+                        owl::ClassExpression::ObjectIntersectionOf(operands) => {
+                            for operand in operands.iter().filter_map(|ce| self.differentia(ce)) {
+                                clauses.push((
+                                    subject.clone(),
+                                    format!("intersection_of: {}", operand),
+                                ));
+                            }
+                        }
+                        owl::ClassExpression::ObjectUnionOf(operands) => {
+                            for operand in operands.iter().filter_map(|ce| self.differentia(ce)) {
+                                clauses.push((subject.clone(), format!("union_of: {}", operand)));
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            owl::Component::DisjointClasses(ax) => {
+                if let (Some(Class(cls)), Some(Class(other))) = (ax.0.first(), ax.0.get(1)) {
+                    clauses.push((
+                        cls.0.to_string(),
+                        format!("disjoint_from: {}", self.ident(other.0.as_ref())),
+                    ));
+                }
+            }
+            owl::Component::SubObjectPropertyOf(ax) => match (&ax.sub, &ax.sup) {
+                (Sub::ObjectPropertyExpression(ObjectProperty(sub)), ObjectProperty(sup)) => {
+                    clauses.push((
+                        sub.0.to_string(),
+                        format!("is_a: {}", self.ident(sup.0.as_ref())),
+                    ));
+                }
+                (Sub::ObjectPropertyChain(chain), ObjectProperty(sup)) => {
+                    if let [ObjectProperty(r1), ObjectProperty(r2)] = chain.as_slice() {
+                        if r1 == sup {
+                            clauses.push((
+                                sup.0.to_string(),
+                                format!("transitive_over: {}", self.ident(r2.0.as_ref())),
+                            ));
+                        } else {
+                            clauses.push((
+                                sup.0.to_string(),
+                                format!(
+                                    "holds_over_chain: {} {}",
+                                    self.ident(r1.0.as_ref()),
+                                    self.ident(r2.0.as_ref())
+                                ),
+                            ));
+                        }
+                    }
+                }
+                _ => (),
+            },
+            owl::Component::SubAnnotationPropertyOf(ax) => {
+                clauses.push((
+                    ax.sub.0.to_string(),
+                    format!("is_a: {}", self.ident(ax.sup.0.as_ref())),
+                ));
+            }
+            owl::Component::EquivalentObjectProperties(ax) => {
+                if let [ObjectProperty(op), ObjectProperty(other)] = ax.0.as_slice() {
+                    clauses.push((
+                        op.0.to_string(),
+                        format!("equivalent_to: {}", self.ident(other.0.as_ref())),
+                    ));
+                }
+            }
+            owl::Component::DisjointObjectProperties(ax) => {
+                if let [ObjectProperty(op), ObjectProperty(other)] = ax.0.as_slice() {
+                    clauses.push((
+                        op.0.to_string(),
+                        format!("disjoint_from: {}", self.ident(other.0.as_ref())),
+                    ));
+                }
+            }
+            owl::Component::InverseObjectProperties(ax) => {
+                clauses.push((
+                    ax.0 .0.to_string(),
+                    format!("inverse_of: {}", self.ident(ax.1 .0.as_ref())),
+                ));
+            }
+            owl::Component::ObjectPropertyDomain(ax) => {
+                if let (ObjectProperty(op), Class(c)) = (&ax.ope, &ax.ce) {
+                    clauses.push((
+                        op.0.to_string(),
+                        format!("domain: {}", self.ident(c.0.as_ref())),
+                    ));
+                }
+            }
+            owl::Component::ObjectPropertyRange(ax) => {
+                if let (ObjectProperty(op), Class(c)) = (&ax.ope, &ax.ce) {
+                    clauses.push((
+                        op.0.to_string(),
+                        format!("range: {}", self.ident(c.0.as_ref())),
+                    ));
+                }
+            }
+            owl::Component::TransitiveObjectProperty(owl::TransitiveObjectProperty(
+                ObjectProperty(op),
+            )) => {
+                clauses.push((op.0.to_string(), String::from("is_transitive: true")));
+            }
+            owl::Component::SymmetricObjectProperty(owl::SymmetricObjectProperty(
+                ObjectProperty(op),
+            )) => {
+                clauses.push((op.0.to_string(), String::from("is_symmetric: true")));
+            }
+            owl::Component::AsymmetricObjectProperty(owl::AsymmetricObjectProperty(
+                ObjectProperty(op),
+            )) => {
+                clauses.push((op.0.to_string(), String::from("is_asymmetric: true")));
+            }
+            owl::Component::ReflexiveObjectProperty(owl::ReflexiveObjectProperty(
+                ObjectProperty(op),
+            )) => {
+                clauses.push((op.0.to_string(), String::from("is_reflexive: true")));
+            }
+            owl::Component::FunctionalObjectProperty(owl::FunctionalObjectProperty(
+                ObjectProperty(op),
+            )) => {
+                clauses.push((op.0.to_string(), String::from("is_functional: true")));
+            }
+            owl::Component::InverseFunctionalObjectProperty(
+                owl::InverseFunctionalObjectProperty(ObjectProperty(op)),
+            ) => {
+                clauses.push((
+                    op.0.to_string(),
+                    String::from("is_inverse_functional: true"),
+                ));
+            }
+            _ => (),
+        }
+        clauses
+    }
+
+    /// Translate the ontology back to an OBO document.
+    fn into_obo(self) -> obo::OboDoc {
+        let mut header = String::new();
+        let mut frames: BTreeMap<String, (&str, Vec<String>)> = BTreeMap::new();
+        let mut subsetdefs = BTreeMap::new();
+        let mut synonymtypedefs = BTreeMap::new();
+
+        // Create a frame for each entity with an OBO identifier.
+        for ac in self.ontology.iter() {
+            let (iri, kind) = match &ac.component {
+                owl::Component::DeclareClass(d) => (d.0 .0.to_string(), "Term"),
+                owl::Component::DeclareObjectProperty(d) => (d.0 .0.to_string(), "Typedef"),
+                owl::Component::DeclareDataProperty(d) => (d.0 .0.to_string(), "Typedef"),
+                owl::Component::DeclareAnnotationProperty(d) => (d.0 .0.to_string(), "Typedef"),
+                _ => continue,
+            };
+            if self.ids.contains_key(&iri) {
+                let frame = frames.entry(iri.clone()).or_insert((kind, Vec::new()));
+                if self.anonymous.contains(&iri) {
+                    frame.1.push(String::from("is_anonymous: true"));
+                }
+            }
+        }
+
+        // Metadata tags are the typedefs only declared as annotation properties.
+        for (iri, (_, clauses)) in frames.iter_mut() {
+            let declarations = self
+                .ontology
+                .iter()
+                .filter_map(|ac| match &ac.component {
+                    owl::Component::DeclareObjectProperty(d) if d.0 .0.as_ref() == iri => {
+                        Some(false)
+                    }
+                    owl::Component::DeclareDataProperty(d) if d.0 .0.as_ref() == iri => Some(false),
+                    owl::Component::DeclareAnnotationProperty(d) if d.0 .0.as_ref() == iri => {
+                        Some(true)
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if !declarations.is_empty() && declarations.iter().all(|annotation| *annotation) {
+                clauses.push(String::from("is_metadata_tag: true"));
+            }
+        }
+
+        // Record the subset and synonym type properties.
+        for ac in self.ontology.iter() {
+            if let owl::Component::SubAnnotationPropertyOf(ax) = &ac.component {
+                match ax.sup.0.as_ref() {
+                    property::obo_in_owl::SUBSET_PROPERTY => {
+                        subsetdefs.insert(ax.sub.0.to_string(), String::new());
+                    }
+                    property::obo_in_owl::SYNONYM_TYPE_PROPERTY => {
+                        synonymtypedefs.insert(ax.sub.0.to_string(), (String::new(), None));
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        // Translate the axioms to clauses of their frame.
+        for ac in self.ontology.iter() {
+            match &ac.component {
+                owl::Component::OntologyAnnotation(owl::OntologyAnnotation(ann)) => {
+                    let clause = match ann.ap.0.as_ref() {
+                        // NB: the data version is recovered from the version IRI.
+                        property::owl::VERSION_INFO if self.version_iri.is_some() => continue,
+                        property::obo_in_owl::HAS_OBO_FORMAT_VERSION => {
+                            format!("format-version: {}", literal(&ann.av).unwrap_or_default())
+                        }
+                        property::obo_in_owl::HAS_DEFAULT_NAMESPACE => {
+                            format!(
+                                "default-namespace: {}",
+                                literal(&ann.av).unwrap_or_default()
+                            )
+                        }
+                        property::obo_in_owl::HAS_DATE => {
+                            format!("date: {}", obo_date(literal(&ann.av).unwrap_or_default()))
+                        }
+                        property::obo_in_owl::SAVED_BY => format!(
+                            "saved-by: {}",
+                            obo::UnquotedString::new(literal(&ann.av).unwrap_or_default())
+                        ),
+                        property::obo_in_owl::AUTO_GENERATED_BY => format!(
+                            "auto-generated-by: {}",
+                            obo::UnquotedString::new(literal(&ann.av).unwrap_or_default())
+                        ),
+                        property::obo_in_owl::NAMESPACE_ID_RULE => format!(
+                            "namespace-id-rule: {}",
+                            obo::UnquotedString::new(literal(&ann.av).unwrap_or_default())
+                        ),
+                        property::rdfs::COMMENT => format!(
+                            "remark: {}",
+                            obo::UnquotedString::new(literal(&ann.av).unwrap_or_default())
+                        ),
+                        _ => match self.annotation(ann, &ac.ann) {
+                            Some(clause) => clause,
+                            None => continue,
+                        },
+                    };
+                    writeln!(header, "{}", clause).unwrap();
+                }
+                owl::Component::Import(import) => {
+                    writeln!(header, "import: {}", import.0).unwrap();
+                }
+                owl::Component::AnnotationAssertion(aa) => {
+                    let subject = match &aa.subject {
+                        owl::AnnotationSubject::IRI(iri) => iri.to_string(),
+                        _ => continue,
+                    };
+                    if let Some(def) = subsetdefs.get_mut(&subject) {
+                        if aa.ann.ap.0.as_ref() == property::rdfs::COMMENT {
+                            *def = literal(&aa.ann.av).unwrap_or_default().to_string();
+                        }
+                    } else if let Some(def) = synonymtypedefs.get_mut(&subject) {
+                        match aa.ann.ap.0.as_ref() {
+                            property::rdfs::LABEL => {
+                                def.0 = literal(&aa.ann.av).unwrap_or_default().to_string()
+                            }
+                            property::obo_in_owl::HAS_SCOPE => {
+                                def.1 = iri(&aa.ann.av).and_then(scope).map(String::from)
+                            }
+                            _ => (),
+                        }
+                    } else if let Some(frame) = frames.get_mut(&subject) {
+                        frame.1.extend(self.annotation(&aa.ann, &ac.ann));
+                    }
+                }
+                other => {
+                    for (subject, clause) in self.logical(other) {
+                        if let Some(frame) = frames.get_mut(&subject) {
+                            frame.1.push(clause);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Write the header frame.
+        writeln!(header, "ontology: {}", self.ontology()).unwrap();
+        if let Some(version) = self.data_version() {
+            writeln!(header, "data-version: {}", version).unwrap();
+        }
+        for (iri, desc) in subsetdefs.iter() {
+            writeln!(
+                header,
+                "subsetdef: {} {}",
+                self.ident(iri),
+                obo::QuotedString::new(desc.as_str())
+            )
+            .unwrap();
+        }
+        for (iri, (desc, scope)) in synonymtypedefs.iter() {
+            let scope = scope
+                .as_ref()
+                .map(|s| format!(" {}", s))
+                .unwrap_or_default();
+            writeln!(
+                header,
+                "synonymtypedef: {} {}{}",
+                self.ident(iri),
+                obo::QuotedString::new(desc.as_str()),
+                scope
+            )
+            .unwrap();
+        }
+
+        // Write the entity frames.
+        let mut text = header;
+        for (iri, (kind, clauses)) in frames.iter() {
+            writeln!(text, "\n[{}]\nid: {}", kind, self.ident(iri)).unwrap();
+            for clause in clauses {
+                writeln!(text, "{}", clause).unwrap();
+            }
+        }
+
+        match fastobo::from_str(&text) {
+            Ok(doc) => doc,
+            Err(e) => panic!("could not parse round-tripped document: {}\n{}", e, text),
+        }
+    }
+}
+
+/// Get the text of a literal annotation value.
+fn literal(av: &owl::AnnotationValue<String>) -> Option<&str> {
+    match av {
+        owl::AnnotationValue::Literal(owl::Literal::Simple { literal })
+        | owl::AnnotationValue::Literal(owl::Literal::Datatype { literal, .. })
+        | owl::AnnotationValue::Literal(owl::Literal::Language { literal, .. }) => Some(literal),
+        _ => None,
+    }
+}
+
+/// Convert an `xsd:dateTime` literal to an OBO header date.
+fn obo_date(datetime: &str) -> String {
+    let (date, time) = datetime.split_once('T').unwrap_or((datetime, "00:00"));
+    let mut parts = date.splitn(3, '-');
+    let (year, month, day) = (parts.next(), parts.next(), parts.next());
+    format!(
+        "{}:{}:{} {}",
+        day.unwrap_or_default(),
+        month.unwrap_or_default(),
+        year.unwrap_or_default(),
+        time.get(..5).unwrap_or(time)
+    )
+}
+
+/// Get the OBO synonym scope of a synonym property.
+fn scope(property: &str) -> Option<&'static str> {
+    match property {
+        property::obo_in_owl::HAS_EXACT_SYNONYM => Some("EXACT"),
+        property::obo_in_owl::HAS_BROAD_SYNONYM => Some("BROAD"),
+        property::obo_in_owl::HAS_NARROW_SYNONYM => Some("NARROW"),
+        property::obo_in_owl::HAS_RELATED_SYNONYM => Some("RELATED"),
+        _ => None,
+    }
+}
+
+/// Get the IRI of an IRI annotation value.
+fn iri(av: &owl::AnnotationValue<String>) -> Option<&str> {
+    match av {
+        owl::AnnotationValue::IRI(iri) => Some(iri.as_ref()),
+        _ => None,
+    }
+}
+
+// --- Tests -----------------------------------------------------------------
+
+/// Translate an OBO document to OWL and back, and report the losses.
+fn roundtrip(path: &Path) -> Report {
+    let doc = fastobo::from_file(path).expect("could not parse input file");
+    let prefixes = doc.prefixes();
+    let ont = doc
+        .clone()
+        .into_owl::<SetOntology<String>>()
+        .expect("could not convert ontology to OWL");
+    let back = Reverse::new(&ont, prefixes).into_obo();
+    Report::new(&normalise(doc), &normalise(back))
+}
+
+/// Check the round-trip of documents only lost the expected clauses.
+fn check<P: AsRef<Path>>(paths: &[P]) {
+    let mut message = String::new();
+    for path in paths.iter().map(AsRef::as_ref) {
+        let report = roundtrip(path);
+        let unexpected = report.unexpected();
+        if !unexpected.is_empty() {
+            writeln!(message, "unexpected differences for {}:", path.display()).unwrap();
+            for (kind, tag, clauses) in unexpected {
+                writeln!(message, "- {} {} ({})", kind, tag, clauses.len()).unwrap();
+                for clause in clauses.iter().take(5) {
+                    writeln!(message, "    {}", clause).unwrap();
+                }
+            }
+        }
+    }
+    if !message.is_empty() {
+        panic!("{}", message);
+    }
+}
+
+#[test]
+fn fixtures() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/into_owl");
+    let mut paths = std::fs::read_dir(&dir)
+        .expect("could not read fixtures directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().ends_with(".input.obo"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    check(&paths);
+}
+
+#[test]
+fn ms() {
+    check(&[PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/ms.obo")]);
+}